@@ -1,32 +1,129 @@
+use crate::landscape::energies::EnergyUnit;
+use crate::landscape::path::parse_path;
+use crate::landscape::{QmEngine, SurfaceFit};
+use crate::plot::Projection;
+use crate::sixring::conformers::default_ring_atoms;
+use crate::sixring::sphere_distributions::SphereDistribution;
+use crate::sixring::sphere_regions::SphereRegion;
 use crate::torsion_typing::TorsionType;
 use std::slice::Iter;
 use std::{panic, println, process::exit};
 
 /// Cli arguments struct
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Flags {
     pub torsion_type: Option<TorsionType>,
     pub num: u64,
+    pub sphere: SphereDistribution,
+    pub regions: Vec<SphereRegion>,
+    pub labels: bool,
+    pub ring_atoms: [String; 6],
+    pub amplitude: Option<f64>,
+    pub bond_lengths: Option<Vec<f64>>,
+    pub chi_num: Option<u64>,
+    pub ring_size: usize,
+    pub product: Vec<(TorsionType, u64)>,
+    pub threads: Option<usize>,
+    pub collect: Option<String>,
+    pub engine: QmEngine,
+    pub landscape: Option<String>,
+    pub unit: EnergyUnit,
+    pub reference: Option<usize>,
+    pub temperature: Option<f64>,
+    pub query: Vec<[f64; 2]>,
+    pub resample: Option<u64>,
+    pub cmap: Option<u64>,
+    pub subtract: Option<String>,
+    pub fit: Option<SurfaceFit>,
+    pub coefficients: Option<String>,
+    pub stationary: bool,
+    pub refine: bool,
+    pub path: Option<[[f64; 2]; 2]>,
+    pub phase: bool,
+    pub svg: Option<String>,
+    pub projection: Projection,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Flags {
     /// Initialise Flags::new() -> Flags
-    /// ```
+    /// ```text
     /// Flags {
     ///     torsion_type : None,
-    ///     num : 0
+    ///     num : 0,
+    ///     sphere : SphereDistribution::Deserno,
+    ///     regions : vec![],
+    ///     labels : false,
+    ///     ring_atoms : ["O", "1", "2", "3", "4", "5"],
+    ///     amplitude : None,
+    ///     bond_lengths : None,
+    ///     chi_num : None,
+    ///     ring_size : 0,
+    ///     product : vec![],
+    ///     threads : None,
+    ///     collect : None,
+    ///     engine : QmEngine::Orca,
+    ///     landscape : None,
+    ///     unit : EnergyUnit::KcalMol,
+    ///     reference : None,
+    ///     temperature : None,
+    ///     query : vec![],
+    ///     resample : None,
+    ///     cmap : None,
+    ///     subtract : None,
+    ///     fit : None,
+    ///     coefficients : None,
+    ///     stationary : false,
+    ///     refine : false,
+    ///     path : None,
+    ///     phase : false,
+    ///     svg : None,
+    ///     projection : Projection::Hammer,
     /// }
     /// ```
     pub fn new() -> Flags {
         Flags {
             torsion_type: None,
             num: 0,
+            sphere: SphereDistribution::Deserno,
+            regions: vec![],
+            labels: false,
+            ring_atoms: default_ring_atoms(),
+            amplitude: None,
+            bond_lengths: None,
+            chi_num: None,
+            ring_size: 0,
+            product: vec![],
+            threads: None,
+            collect: None,
+            engine: QmEngine::Orca,
+            landscape: None,
+            unit: EnergyUnit::KcalMol,
+            reference: None,
+            temperature: None,
+            query: vec![],
+            resample: None,
+            cmap: None,
+            subtract: None,
+            fit: None,
+            coefficients: None,
+            stationary: false,
+            refine: false,
+            path: None,
+            phase: false,
+            svg: None,
+            projection: Projection::Hammer,
         }
     }
 
     /// add Torsion type to the Flags Struct
     /// add num amount to the Flags Struct
-    fn define_torsion_type(&mut self, torsion: TorsionType, iter: &mut Iter<'_, String>) -> () {
+    fn define_torsion_type(&mut self, torsion: TorsionType, iter: &mut Iter<'_, String>) {
         self.torsion_type = Some(torsion);
 
         self.num = match iter.next() {
@@ -38,6 +135,293 @@ impl Flags {
         }
     }
 
+    /// add the amount of atoms of the ring to the Flags Struct, followed by the num amount
+    fn define_ring(&mut self, iter: &mut Iter<'_, String>) {
+        self.ring_size = match iter.next() {
+            Some(a) => match a.parse::<usize>() {
+                Ok(size) if size >= 4 => size,
+                _ => panic!("`N` not parsed as an integer of at least 4... Aborting."),
+            },
+            None => panic!("End of query, no `N` value prompted."),
+        };
+        self.define_torsion_type(TorsionType::Ring, iter)
+    }
+
+    /// add the distribution of points on the sphere to the Flags Struct
+    fn define_sphere_distribution(&mut self, iter: &mut Iter<'_, String>) {
+        self.sphere = match iter.next() {
+            Some(a) => match SphereDistribution::from_name(a) {
+                Some(distribution) => distribution,
+                None => panic!("`{}` is not a known sphere distribution... Aborting.", a),
+            },
+            None => panic!("End of query, no sphere distribution prompted."),
+        }
+    }
+
+    /// add a region of the sphere to restrict the sampling to
+    fn define_sphere_region(&mut self, iter: &mut Iter<'_, String>) {
+        match iter.next() {
+            Some(a) => match SphereRegion::from_query(a) {
+                Ok(region) => self.regions.push(region),
+                Err(e) => panic!("{}... Aborting.", e),
+            },
+            None => panic!("End of query, no region prompted."),
+        }
+    }
+
+    /// add the names of the six ring atoms, used to write out the conformer labels
+    fn define_ring_atoms(&mut self, iter: &mut Iter<'_, String>) {
+        let names: Vec<String> = match iter.next() {
+            Some(a) => a.split(',').map(|n| n.trim().to_string()).collect(),
+            None => panic!("End of query, no ring atoms prompted."),
+        };
+
+        self.ring_atoms = match names.try_into() {
+            Ok(atoms) => atoms,
+            Err(_) => {
+                panic!("`--ring-atoms` requires exactly six comma-separated names... Aborting.")
+            }
+        }
+    }
+
+    /// add the total puckering amplitude to the Flags Struct
+    fn define_amplitude(&mut self, iter: &mut Iter<'_, String>) {
+        self.amplitude = match iter.next() {
+            Some(a) => match a.parse::<f64>() {
+                Ok(q) => Some(q),
+                Err(_) => panic!("`amplitude` not parsed as a number... Aborting."),
+            },
+            None => panic!("End of query, no `amplitude` value prompted."),
+        }
+    }
+
+    /// add the bond lengths of the ring to the Flags Struct, either `R` or `R1,R2,..,RN`
+    fn define_bond_lengths(&mut self, iter: &mut Iter<'_, String>) {
+        self.bond_lengths = match iter.next() {
+            Some(a) => Some(
+                a.split(',')
+                    .map(|r| match r.trim().parse::<f64>() {
+                        Ok(r) if r > 0. => r,
+                        _ => panic!("`bond length` not parsed as a positive number... Aborting."),
+                    })
+                    .collect(),
+            ),
+            None => panic!("End of query, no `bond length` value prompted."),
+        }
+    }
+
+    /// add the amount of values on the chi axis to the Flags Struct
+    fn define_chi_amount(&mut self, iter: &mut Iter<'_, String>) {
+        self.chi_num = match iter.next() {
+            Some(a) => match a.parse::<u64>() {
+                Ok(num) => Some(num),
+                Err(_) => panic!("`chi` not parsed as integer... Aborting."),
+            },
+            None => panic!("End of query, no `chi` value prompted."),
+        }
+    }
+
+    /// add the factors of a product of sampling spaces to the Flags Struct
+    /// e.g. `peptide:13,fivering:7,sixring:100`
+    fn define_product(&mut self, iter: &mut Iter<'_, String>) {
+        self.torsion_type = Some(TorsionType::Product);
+
+        let query = match iter.next() {
+            Some(a) => a,
+            None => panic!("End of query, no `product` prompted."),
+        };
+
+        for factor in query.split(',') {
+            let (name, num) = match factor.split_once(':') {
+                Some(pair) => pair,
+                None => panic!("`{}` is not written as SPACE:NUM... Aborting.", factor),
+            };
+
+            let torsion_type = match name.trim().to_lowercase().as_str() {
+                "peptide" => TorsionType::Peptide,
+                "fivering" => TorsionType::Fivering,
+                "sixring" => TorsionType::Sixring,
+                _ => panic!(
+                    "`{}` is not a space that can be combined... Aborting.",
+                    name
+                ),
+            };
+
+            let num = match num.trim().parse::<u64>() {
+                Ok(num) => num,
+                Err(_) => panic!("`num` of `{}` not parsed as integer... Aborting.", name),
+            };
+
+            self.product.push((torsion_type, num))
+        }
+    }
+
+    /// add the amount of threads to the Flags Struct
+    fn define_threads(&mut self, iter: &mut Iter<'_, String>) {
+        self.threads = match iter.next() {
+            Some(a) => match a.parse::<usize>() {
+                Ok(num) if num > 0 => Some(num),
+                _ => panic!("`threads` not parsed as a positive integer... Aborting."),
+            },
+            None => panic!("End of query, no `threads` value prompted."),
+        }
+    }
+
+    /// add the path to the job manifest to the Flags Struct
+    fn define_collect(&mut self, iter: &mut Iter<'_, String>) {
+        self.collect = match iter.next() {
+            Some(a) => Some(a.to_string()),
+            None => panic!("End of query, no `manifest` prompted."),
+        }
+    }
+
+    /// add the QM engine that ran the collected jobs to the Flags Struct
+    fn define_engine(&mut self, iter: &mut Iter<'_, String>) {
+        self.engine = match iter.next() {
+            Some(a) => match QmEngine::from_name(a) {
+                Some(engine) => engine,
+                None => panic!(
+                    "`{}` is not a known QM engine (orca, gaussian, xtb)... Aborting.",
+                    a
+                ),
+            },
+            None => panic!("End of query, no `engine` prompted."),
+        }
+    }
+
+    /// add the path to the landscape table to the Flags Struct
+    fn define_landscape(&mut self, iter: &mut Iter<'_, String>) {
+        self.landscape = match iter.next() {
+            Some(a) => Some(a.to_string()),
+            None => panic!("End of query, no `landscape` table prompted."),
+        }
+    }
+
+    /// add the unit of the relative energies to the Flags Struct
+    fn define_unit(&mut self, iter: &mut Iter<'_, String>) {
+        self.unit = match iter.next() {
+            Some(a) => match EnergyUnit::from_name(a) {
+                Some(unit) => unit,
+                None => panic!(
+                    "`{}` is not a known unit (kcal, kj, ev, hartree)... Aborting.",
+                    a
+                ),
+            },
+            None => panic!("End of query, no `unit` prompted."),
+        }
+    }
+
+    /// add the index of the reference point to the Flags Struct
+    fn define_reference(&mut self, iter: &mut Iter<'_, String>) {
+        self.reference = match iter.next() {
+            Some(a) => match a.parse::<usize>() {
+                Ok(index) => Some(index),
+                Err(_) => panic!("`reference` not parsed as an index... Aborting."),
+            },
+            None => panic!("End of query, no `reference` value prompted."),
+        }
+    }
+
+    /// add the temperature of the Boltzmann populations to the Flags Struct
+    fn define_temperature(&mut self, iter: &mut Iter<'_, String>) {
+        self.temperature = match iter.next() {
+            Some(a) => match a.parse::<f64>() {
+                Ok(t) if t > 0. => Some(t),
+                _ => panic!("`temperature` not parsed as a positive number... Aborting."),
+            },
+            None => panic!("End of query, no `temperature` value prompted."),
+        }
+    }
+
+    /// add a point to evaluate the interpolated landscape at to the Flags Struct
+    fn define_query(&mut self, iter: &mut Iter<'_, String>) {
+        let values: Vec<f64> = match iter.next() {
+            Some(a) => a
+                .split(',')
+                .map(|v| match v.trim().parse::<f64>() {
+                    Ok(v) => v,
+                    Err(_) => panic!("`query` not parsed as two numbers `A,B`... Aborting."),
+                })
+                .collect(),
+            None => panic!("End of query, no `query` point prompted."),
+        };
+
+        match values[..] {
+            [a, b] => self.query.push([a, b]),
+            _ => panic!("`query` requires exactly two comma-separated numbers... Aborting."),
+        }
+    }
+
+    /// add the amount of points of the resampled landscape to the Flags Struct
+    fn define_resample(&mut self, iter: &mut Iter<'_, String>) {
+        self.resample = Some(parse_grid_size(iter.next(), "resample"))
+    }
+
+    /// add the amount of points of the CMAP table to the Flags Struct
+    fn define_cmap(&mut self, iter: &mut Iter<'_, String>) {
+        self.cmap = Some(parse_grid_size(iter.next(), "cmap"))
+    }
+
+    /// add the path to the landscape table to subtract to the Flags Struct
+    fn define_subtract(&mut self, iter: &mut Iter<'_, String>) {
+        self.subtract = match iter.next() {
+            Some(a) => Some(a.to_string()),
+            None => panic!("End of query, no `subtract` table prompted."),
+        }
+    }
+
+    /// add the model to fit the landscape with to the Flags Struct
+    fn define_fit(&mut self, iter: &mut Iter<'_, String>) {
+        self.fit = match iter.next() {
+            Some(a) => match SurfaceFit::from_query(a) {
+                Ok(fit) => Some(fit),
+                Err(e) => panic!("{}... Aborting.", e),
+            },
+            None => panic!("End of query, no `fit` model prompted."),
+        }
+    }
+
+    /// add the ends of the path on the sixring sphere to the Flags Struct
+    fn define_path(&mut self, iter: &mut Iter<'_, String>) {
+        self.path = match iter.next() {
+            Some(a) => match parse_path(a) {
+                Ok(ends) => Some(ends),
+                Err(e) => panic!("{}... Aborting.", e),
+            },
+            None => panic!("End of query, no `path` ends prompted."),
+        }
+    }
+
+    /// add the path to write the coefficients of the fit to, to the Flags Struct
+    fn define_coefficients(&mut self, iter: &mut Iter<'_, String>) {
+        self.coefficients = match iter.next() {
+            Some(a) => Some(a.to_string()),
+            None => panic!("End of query, no `coefficients` file prompted."),
+        }
+    }
+
+    /// add the path to write the SVG image to, to the Flags Struct
+    fn define_svg(&mut self, iter: &mut Iter<'_, String>) {
+        self.svg = match iter.next() {
+            Some(a) => Some(a.to_string()),
+            None => panic!("End of query, no `svg` file prompted."),
+        }
+    }
+
+    /// add the projection of the sixring sphere to the Flags Struct
+    fn define_projection(&mut self, iter: &mut Iter<'_, String>) {
+        self.projection = match iter.next() {
+            Some(a) => match Projection::from_name(a) {
+                Some(projection) => projection,
+                None => panic!(
+                    "`{}` is not a projection. Choose equirectangular, hammer or polar... Aborting.",
+                    a
+                ),
+            },
+            None => panic!("End of query, no `projection` prompted."),
+        }
+    }
+
     /// Pass the Vec of Strings, which are the CLI arguments that are given to puckers
     /// and are processed and returned as a neat struct to the main function.
     pub fn return_cli_arguments(cli_args: Vec<String>) -> Flags {
@@ -51,53 +435,183 @@ impl Flags {
 
         let mut cli_iter = cli_args.iter();
 
-        loop {
-            // while the iterator produces valid Some(x) types
-
-            match cli_iter.next() {
-                Some(cli) => {
-                    match &cli[..] {
-                        // from String to &str type
-                        "--peptide" => {
-                            // saveguard if two torsion types have been queried,
-                            // only first one matters
-                            if flag.torsion_type.is_none() {
-                                flag.define_torsion_type(TorsionType::Peptide, &mut cli_iter)
-                            }
-                        }
-                        "--fivering" => {
-                            if flag.torsion_type.is_none() {
-                                flag.define_torsion_type(TorsionType::Fivering, &mut cli_iter)
-                            }
-                        }
-                        "--sixring" => {
-                            if flag.torsion_type.is_none() {
-                                flag.define_torsion_type(TorsionType::Sixring, &mut cli_iter)
-                            }
-                        }
-                        "-h" | "--help" => print_help(),
-                        _ => (), // exhaustive match; do nothing.
-                    }
+        // while the iterator produces valid Some(x) types
+        while let Some(cli) = cli_iter.next() {
+            match &cli[..] {
+                // from String to &str type
+                // saveguard if two torsion types have been queried,
+                // only first one matters
+                "--fourring" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Fourring, &mut cli_iter)
                 }
-                // end of cli argument query
-                None => break,
+                "--peptide" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Peptide, &mut cli_iter)
+                }
+                "--fivering" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Fivering, &mut cli_iter)
+                }
+                "--sixring" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Sixring, &mut cli_iter)
+                }
+                "--sevenring" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Sevenring, &mut cli_iter)
+                }
+                "--ring" if flag.torsion_type.is_none() => flag.define_ring(&mut cli_iter),
+                "--nucleoside" if flag.torsion_type.is_none() => {
+                    flag.define_torsion_type(TorsionType::Nucleoside, &mut cli_iter)
+                }
+                "--product" if flag.torsion_type.is_none() => flag.define_product(&mut cli_iter),
+                "--sphere" => flag.define_sphere_distribution(&mut cli_iter),
+                "--region" => flag.define_sphere_region(&mut cli_iter),
+                "--labels" => flag.labels = true,
+                "--ring-atoms" => flag.define_ring_atoms(&mut cli_iter),
+                "--amplitude" => flag.define_amplitude(&mut cli_iter),
+                "--bond-length" => flag.define_bond_lengths(&mut cli_iter),
+                "--chi" => flag.define_chi_amount(&mut cli_iter),
+                "--threads" => flag.define_threads(&mut cli_iter),
+                "--collect" => flag.define_collect(&mut cli_iter),
+                "--engine" => flag.define_engine(&mut cli_iter),
+                "--landscape" => flag.define_landscape(&mut cli_iter),
+                "--unit" => flag.define_unit(&mut cli_iter),
+                "--reference" => flag.define_reference(&mut cli_iter),
+                "--temperature" => flag.define_temperature(&mut cli_iter),
+                "--query" => flag.define_query(&mut cli_iter),
+                "--resample" => flag.define_resample(&mut cli_iter),
+                "--cmap" => flag.define_cmap(&mut cli_iter),
+                "--subtract" => flag.define_subtract(&mut cli_iter),
+                "--fit" => flag.define_fit(&mut cli_iter),
+                "--coefficients" => flag.define_coefficients(&mut cli_iter),
+                "--stationary" => flag.stationary = true,
+                "--refine" => flag.refine = true,
+                "--path" => flag.define_path(&mut cli_iter),
+                "--phase" => flag.phase = true,
+                "--svg" => flag.define_svg(&mut cli_iter),
+                "--projection" => flag.define_projection(&mut cli_iter),
+                "-h" | "--help" => print_help(),
+                _ => (), // exhaustive match; do nothing.
             }
         }
 
-        if flag.torsion_type.is_none() {
+        // a landscape table already holds its grid
+        if flag.torsion_type.is_none() && flag.landscape.is_none() {
             panic!("No `torsion type` queried... Aborting.")
         }
 
+        // only the sphere and the pseudorotation plane can be followed along a path
+        if flag.path.is_some()
+            && flag.landscape.is_none()
+            && !matches!(
+                flag.torsion_type,
+                Some(TorsionType::Sixring) | Some(TorsionType::Fivering)
+            )
+        {
+            panic!("`--path` can only be prompted with --sixring or --fivering... Aborting.")
+        }
+
+        // the factors of a product each have their own kind of label, or none at all
+        if flag.labels && flag.torsion_type == Some(TorsionType::Product) {
+            panic!("`--labels` can not be prompted with --product... Aborting.")
+        }
+
+        // only the two-dimensional grids and the sphere can be drawn
+        if flag.svg.is_some()
+            && flag.landscape.is_none()
+            && !matches!(
+                flag.torsion_type,
+                Some(TorsionType::Peptide)
+                    | Some(TorsionType::Fivering)
+                    | Some(TorsionType::Sixring)
+            )
+        {
+            panic!(
+                "`--svg` can only be prompted with --peptide, --fivering or --sixring... Aborting."
+            )
+        }
+
         flag
     }
 }
 
+/// The amount of points along an axis of a grid, which needs at least two
+fn parse_grid_size(value: Option<&String>, name: &str) -> u64 {
+    match value {
+        Some(a) => match a.parse::<u64>() {
+            Ok(num) if num > 1 => num,
+            _ => panic!("`{}` not parsed as an integer above 1... Aborting.", name),
+        },
+        None => panic!("End of query, no `{}` value prompted.", name),
+    }
+}
+
 fn print_help() {
     println!(
         "Pucke.rs help menu :
-       --peptide  NUM : to generate torsion angles for peptide-like systems
-       --fivering NUM : to generate torsion angles for five-membered ring systems
-       --sixring  NUM : to generate torsion angles for six-membered ring systems
+       --peptide    NUM : to generate torsion angles for peptide-like systems
+       --fourring   NUM : to generate torsion angles for four-membered ring systems
+       --fivering   NUM : to generate torsion angles for five-membered ring systems
+       --sixring    NUM : to generate torsion angles for six-membered ring systems
+       --sevenring  NUM : to generate torsion angles for seven-membered ring systems
+       --ring     N NUM : to generate torsion angles for any N-membered ring system (N >= 4)
+       --nucleoside NUM : to generate the torsion angles of the sugar (as --fivering NUM)
+                          together with the glycosidic torsion angle chi
+       --product   SPEC : to generate the Cartesian product of several sampling spaces.
+                          SPACE:NUM,SPACE:NUM,.. with peptide, fivering or sixring
+                          (e.g. peptide:13,sixring:100)
+       --sphere  NAME : distribution of points for --sixring.
+                        deserno (default), fibonacci, healpix or icosahedron
+       --region  AREA : restrict --sixring to a region of the sphere (degrees).
+                        theta:MIN,MAX | phi:MIN,MAX | cap:THETA,PHI,RADIUS
+                        | conformer:LABEL,RADIUS (e.g. conformer:4C1,30)
+       --labels       : add the nearest IUPAC conformer (and its distance) to --sixring,
+                        or the pseudorotation phase, form and N/E/S/W sector to --fivering
+                        and --nucleoside
+       --ring-atoms A : names of the six ring atoms in the labels (default: O,1,2,3,4,5)
+       --amplitude  Q : total puckering amplitude (Angstrom) for --sixring (default: 0.67)
+                        or --sevenring (default: 0.80),
+                        or the largest amplitude q2 for --fourring (default: 0.25),
+                        or --ring (default: as above for N = 4 to 7, 1.00 for N > 7)
+       --bond-length R : bond length (Angstrom) of the ring for --fourring, --sevenring or --ring
+                         (default: 1.54), or one per bond, R1,R2,..,RN, where bond j runs from
+                         atom j to atom j+1
+       --chi       NUM : amount of chi values in [0, 360) for --nucleoside (default: NUM)
+       --threads   NUM : amount of threads to generate the conformations with (default: all cores).
+                         Requires puckers to be built with `--features parallel`
+       --collect  FILE : collect the energies of the finished QM jobs in the manifest FILE
+                         (lines of `INDEX DIRECTORY`) into a table with the grid coordinates
+       --engine   NAME : the QM engine that ran the jobs of --collect.
+                         orca (default), gaussian or xtb
+       --landscape FILE : post-process a landscape table (the output of --collect) :
+                         add the relative energies (and populations) behind every point
+       --unit     NAME : unit of the relative energies. kcal (default), kj, ev or hartree
+       --reference IDX : INDEX of the point the energies are relative to (default: global minimum)
+       --temperature T : add the Boltzmann populations at T (Kelvin)
+       --query    A,B : evaluate the interpolated --landscape at (phi, psi),
+                        at (P, amplitude) for a fivering or at (theta, phi) in degrees
+                        for a sixring. Can be repeated
+       --resample NUM : resample the interpolated --landscape on a NUM x NUM (phi, psi)
+                        or (Zx, Zy) grid, or on a sphere of NUM points (see --sphere) for a sixring
+       --cmap     NUM : write the interpolated --landscape as a NUM x NUM CHARMM CMAP table
+       --subtract FILE : landscape table (e.g. the force field scan) subtracted for --cmap
+       --fit     MODEL : fit a sixring --landscape on the sphere (default: harmonics:6).
+                         harmonics:L (least squares up to degree L) | rbf:EPS (geodesic),
+                         or a fivering --landscape along the pseudorotation wheel
+                         (default: fourier:3,3). fourier:K,M (harmonics of P up to K <= M,
+                         polynomials of the amplitude up to M)
+       --coefficients FILE : write the coefficients of the fivering --fit to FILE
+       --stationary   : list the local minima and first-order saddle points of a --landscape
+       --refine       : refine the --stationary points on the interpolated (or --fit) surface,
+                        or follow the --path on the fitted surface in between the grid points
+       --path FROM:TO : the path of the lowest barrier between two points of a sixring --landscape.
+                        Either end is a conformer (e.g. 4C1) or THETA,PHI in degrees.
+                        With --sixring NUM, samples NUM points along the great-circle arc instead,
+                        or with --fivering NUM, along the straight line between two points ZX,ZY
+       --phase        : take the --fivering --path ends as P,AMPLITUDE (degrees), and follow
+                        the pseudorotation wheel
+       --svg     FILE : draw the --peptide, --fivering or --sixring grid, or the relative energies
+                        of a --landscape, as an SVG image in FILE
+       --projection NAME : projection of the sixring sphere for --svg.
+                        hammer (default, equal-area), polar (equal-area discs around both chairs)
+                        or equirectangular (phi against theta)
        -h or --help   : to print this menu. "
     );
     exit(0)
@@ -1,9 +1,14 @@
-use std::f64::consts::PI;
+// import module(fivering) modules
+pub mod pseudorotation;
 
-use ndarray::Array1;
+use std::f64::consts::PI;
 
 use crate::arguments::Flags;
-use crate::torsion_typing::{Furanose, FuranoseAxes};
+use crate::fivering::pseudorotation::Pseudorotation;
+use crate::torsion_typing::{
+    names, Furanose, FuranoseAxes, Label, LabelKind, SamplingSpace, FURANOSE_COORDINATES,
+    FURANOSE_TORSIONS,
+};
 
 const FOURPIOVERFIVE: f64 = (4. * PI) / 5.;
 
@@ -18,46 +23,163 @@ const FOURPIOVERFIVE: f64 = (4. * PI) / 5.;
 /// nu1 = ( (zx * 2cos(4pi/5)) + (zy * 2sin(4pi/5)) )/ 2
 /// nu3 = ( (zx * 2cos(4pi/5)) - (zy * 2sin(4pi/5)) )/ 2
 pub fn fivering(flags: &Flags) -> Furanose {
-    // Derive torsion angles from the given axes
-    let polars = FuranoseAxes::new(flags.num as usize);
+    let stream = fivering_stream(flags);
+
+    // Instance Furanose struct
+    let mut f = Furanose::new(stream.len());
+
+    for sample in stream.samples(false) {
+        let i = sample.index;
+
+        // fill out the array
+        f.nu1[i] = sample.torsions[0];
+        f.nu3[i] = sample.torsions[1];
+        f.zx[i] = sample.coordinates[0];
+        f.zy[i] = sample.coordinates[1];
+    }
 
-    // Setup variable
-    let amount: u64 = flags.num * flags.num;
-    let num_f64: f64 = flags.num as f64;
+    f
+}
 
+/// The nu1 and nu3 torsion angles of a single point (Zx, Zy) of the grid
+pub fn furanose_torsions(zx: f64, zy: f64) -> [f64; 2] {
     // Initialise equation-specific constants
     let denominator_x: f64 = FOURPIOVERFIVE.cos();
     let denominator_y: f64 = FOURPIOVERFIVE.sin();
 
-    // Instance Furanose struct
-    let mut f = Furanose::new(amount as usize);
+    let nu1 = (zx * denominator_x) + (zy * denominator_y);
+    let nu3 = (zx * denominator_x) - (zy * denominator_y);
 
-    let mut x: f64;
-    let mut y: f64;
+    // Make values ORCA-ready
+    [nu1, nu3].map(|x| if x < 0. { x + 360. } else { x })
+}
 
-    for i in 0..amount as usize {
-        // Calculate indexes for the array axises
-        x = (i as f64 / num_f64).floor(); // X axis, returns with floor
-        y = i as f64 % num_f64; // Y axis, return with modulo
+/// The furanose sampling space, where every conformation is computed when asked for
+/// public `axes` field : FuranoseAxes
+/// public `num` field : usize. The amount of values on either axis
+pub struct FuranoseStream {
+    pub axes: FuranoseAxes,
+    pub num: usize,
+}
 
-        // fill out the array
-        f.nu1[i] =
-            (polars.zx[x as usize] * denominator_x) + (polars.zy[y as usize] * denominator_y);
-        f.nu3[i] =
-            (polars.zx[x as usize] * denominator_x) - (polars.zy[y as usize] * denominator_y);
+/// Generate the stream of torsion angles for furanose molecules
+pub fn fivering_stream(flags: &Flags) -> FuranoseStream {
+    FuranoseStream {
+        axes: FuranoseAxes::new(flags.num as usize),
+        num: flags.num as usize,
     }
+}
 
-    // Make values ORCA-ready
-    f.nu1 = f
-        .nu1
-        .iter()
-        .map(|x| if x < &0. { x + 360. } else { *x })
-        .collect::<Array1<f64>>();
-    f.nu3 = f
-        .nu3
-        .iter()
-        .map(|x| if x < &0. { x + 360. } else { *x })
-        .collect::<Array1<f64>>();
+impl FuranoseStream {
+    /// The (Zx, Zy) coordinates of the i-th point; the Zx axis runs over the outer index
+    pub fn zx_zy(&self, i: usize) -> (f64, f64) {
+        (self.axes.zx[i / self.num], self.axes.zy[i % self.num])
+    }
+}
 
-    f
+impl SamplingSpace for FuranoseStream {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&FURANOSE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&FURANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.num * self.num
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        let (zx, zy) = self.zx_zy(i);
+        furanose_torsions(zx, zy).to_vec()
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        let (zx, zy) = self.zx_zy(i);
+        vec![zx, zy]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Pseudorotation)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        let (zx, zy) = self.zx_zy(i);
+        Some(Label::Pseudorotation(Pseudorotation::from_zx_zy(zx, zy)))
+    }
+}
+
+/// The furanose conformations along a path in the (Zx, Zy) plane
+/// public `points` field : Vec<(f64, f64)>. The (Zx, Zy) coordinates of every point
+pub struct FuranosePath {
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Generate `num` points along the path between both ends, both included.
+/// The path is a straight line between two points (Zx, Zy), or with `--phase`,
+/// runs along the pseudorotation wheel between two points (P, amplitude) : the phase turns the
+/// shorter way around, while the amplitude changes linearly
+pub fn fivering_path(flags: &Flags, ends: [[f64; 2]; 2]) -> FuranosePath {
+    let [from, to] = ends;
+    let num = flags.num as usize;
+    let fraction = |i: usize| {
+        if num > 1 {
+            i as f64 / (num - 1) as f64
+        } else {
+            0.
+        }
+    };
+
+    let points = (0..num)
+        .map(|i| {
+            let t = fraction(i);
+            if flags.phase {
+                let turn = (to[0] - from[0] + 180.).rem_euclid(360.) - 180.;
+                let phase = (from[0] + t * turn).to_radians();
+                let amplitude = from[1] + t * (to[1] - from[1]);
+                (amplitude * phase.cos(), amplitude * phase.sin())
+            } else {
+                (
+                    from[0] + t * (to[0] - from[0]),
+                    from[1] + t * (to[1] - from[1]),
+                )
+            }
+        })
+        .collect();
+
+    FuranosePath { points }
+}
+
+impl SamplingSpace for FuranosePath {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&FURANOSE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&FURANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        let (zx, zy) = self.points[i];
+        furanose_torsions(zx, zy).to_vec()
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        let (zx, zy) = self.points[i];
+        vec![zx, zy]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Pseudorotation)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        let (zx, zy) = self.points[i];
+        Some(Label::Pseudorotation(Pseudorotation::from_zx_zy(zx, zy)))
+    }
 }
@@ -0,0 +1,235 @@
+/// Describe five-membered rings along the pseudorotation wheel
+///
+/// " Conformational analysis of the sugar ring in nucleosides and nucleotides. New description
+///   using the concept of pseudorotation, Altona, C and Sundaralingam, M "
+/// Journal of the American Chemical Society. doi.org/10.1021/ja00772a043
+///
+/// ```text
+///     nu_j = nu_max * cos(P + 4pi * (j - 2) / 5)
+///
+///     Zx = nu_max * cos(P)
+///     Zy = nu_max * sin(P)
+///
+///     => P = atan2(Zy, Zx) and nu_max = sqrt(Zx^2 + Zy^2)
+/// ```
+///
+/// Every 18 degrees of P, the ring passes an envelope (E) or a twist (T) form.
+/// Atoms written before the letter are endo (above the plane), atoms after it are exo.
+///
+/// ```text
+///     P =   0 : 3T2     P =  90 : OE      P = 180 : 2T3     P = 270 : EO
+///     P =  18 : 3E      P = 108 : OT1     P = 198 : E3      P = 288 : 1TO
+///     P =  36 : 3T4     P = 126 : E1      P = 216 : 4T3     P = 306 : 1E
+///     P =  54 : E4      P = 144 : 2T1     P = 234 : 4E      P = 324 : 1T2
+///     P =  72 : OT4     P = 162 : 2E      P = 252 : 4TO     P = 342 : E2
+/// ```
+///
+/// A ring without amplitude (Zx = Zy = 0) is planar and has no phase, so it gets no form.
+///
+/// The wheel is divided into four sectors : North (C3'-endo) around P = 0, East (O4'-endo)
+/// around P = 90, South (C2'-endo) around P = 180 and West (O4'-exo) around P = 270.
+///
+//
+// import modules
+use std::f64::consts::PI;
+
+/// The step in pseudorotation phase between two consecutive forms, in degrees
+const STEP: f64 = 18.;
+
+/// The amplitude (degrees) below which the ring is taken as planar
+const PLANAR_AMPLITUDE: f64 = 1e-3;
+
+/// An envelope or a twist form of the furanose ring
+/// public `twist` field : bool. A twist (T) form, or else an envelope (E) form
+/// public `endo` field : &str. The atom(s) above the plane
+/// public `exo` field : &str. The atom(s) below the plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuranoseForm {
+    pub twist: bool,
+    pub endo: &'static str,
+    pub exo: &'static str,
+}
+
+impl FuranoseForm {
+    const fn envelope(endo: &'static str, exo: &'static str) -> FuranoseForm {
+        FuranoseForm {
+            twist: false,
+            endo,
+            exo,
+        }
+    }
+
+    const fn twist(endo: &'static str, exo: &'static str) -> FuranoseForm {
+        FuranoseForm {
+            twist: true,
+            endo,
+            exo,
+        }
+    }
+
+    /// The IUPAC label, e.g. `3E`, `E2` or `3T2`
+    pub fn label(&self) -> String {
+        let letter = if self.twist { "T" } else { "E" };
+        format!("{}{}{}", self.endo, letter, self.exo)
+    }
+
+    /// The label as used for nucleosides, e.g. `C3'-endo`, `O4'-exo` or `C3'-endo-C2'-exo`
+    pub fn description(&self) -> String {
+        let mut parts: Vec<String> = Vec::with_capacity(2);
+        if !self.endo.is_empty() {
+            parts.push(format!("{}-endo", sugar_atom(self.endo)))
+        }
+        if !self.exo.is_empty() {
+            parts.push(format!("{}-exo", sugar_atom(self.exo)))
+        }
+        parts.join("-")
+    }
+}
+
+/// The name of the ring atom in a (deoxy)ribose
+fn sugar_atom(locant: &str) -> String {
+    match locant {
+        "O" => "O4'".to_string(),
+        n => format!("C{}'", n),
+    }
+}
+
+/// The 20 forms along the pseudorotation wheel, starting at P = 0 in steps of 18 degrees
+pub const FURANOSE_FORMS: [FuranoseForm; 20] = [
+    FuranoseForm::twist("3", "2"),
+    FuranoseForm::envelope("3", ""),
+    FuranoseForm::twist("3", "4"),
+    FuranoseForm::envelope("", "4"),
+    FuranoseForm::twist("O", "4"),
+    FuranoseForm::envelope("O", ""),
+    FuranoseForm::twist("O", "1"),
+    FuranoseForm::envelope("", "1"),
+    FuranoseForm::twist("2", "1"),
+    FuranoseForm::envelope("2", ""),
+    FuranoseForm::twist("2", "3"),
+    FuranoseForm::envelope("", "3"),
+    FuranoseForm::twist("4", "3"),
+    FuranoseForm::envelope("4", ""),
+    FuranoseForm::twist("4", "O"),
+    FuranoseForm::envelope("", "O"),
+    FuranoseForm::twist("1", "O"),
+    FuranoseForm::envelope("1", ""),
+    FuranoseForm::twist("1", "2"),
+    FuranoseForm::envelope("", "2"),
+];
+
+/// The four quadrants of the pseudorotation wheel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sector {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Sector {
+    /// Return the sector a pseudorotation phase (degrees) lies in
+    pub fn from_phase(phase: f64) -> Sector {
+        match phase.rem_euclid(360.) {
+            p if !(45. ..315.).contains(&p) => Sector::North,
+            p if p < 135. => Sector::East,
+            p if p < 225. => Sector::South,
+            _ => Sector::West,
+        }
+    }
+
+    /// The single letter abbreviation
+    pub fn letter(&self) -> &'static str {
+        match self {
+            Sector::North => "N",
+            Sector::East => "E",
+            Sector::South => "S",
+            Sector::West => "W",
+        }
+    }
+}
+
+/// The pseudorotation parameters of a furanose
+/// public `phase` field : f64. P, in degrees [0, 360)
+/// public `amplitude` field : f64. nu_max, in degrees
+#[derive(Debug, Clone, Copy)]
+pub struct Pseudorotation {
+    pub phase: f64,
+    pub amplitude: f64,
+}
+
+impl Pseudorotation {
+    /// From the Zx and Zy coordinates of the fivering grid
+    pub fn from_zx_zy(zx: f64, zy: f64) -> Pseudorotation {
+        Pseudorotation {
+            phase: zy.atan2(zx).to_degrees().rem_euclid(360.),
+            amplitude: (zx.powi(2) + zy.powi(2)).sqrt(),
+        }
+    }
+
+    /// From the five endocyclic torsion angles nu0 to nu4 (degrees) of an analysed furanose
+    ///
+    /// ```text
+    ///     tan(P) = ((nu4 + nu1) - (nu3 + nu0)) / (2 * nu2 * (sin(36) + sin(72)))
+    ///     nu_max = nu2 / cos(P)
+    /// ```
+    pub fn from_torsions(nu: [f64; 5]) -> Pseudorotation {
+        let numerator = (nu[4] + nu[1]) - (nu[3] + nu[0]);
+        let denominator = 2. * nu[2] * ((PI / 5.).sin() + (2. * PI / 5.).sin());
+        let phase = numerator.atan2(denominator);
+
+        Pseudorotation {
+            phase: phase.to_degrees().rem_euclid(360.),
+            amplitude: nu[2] / phase.cos(),
+        }
+    }
+
+    /// The nearest envelope or twist form
+    pub fn form(&self) -> &'static FuranoseForm {
+        let idx = (self.phase / STEP).round() as usize % FURANOSE_FORMS.len();
+        &FURANOSE_FORMS[idx]
+    }
+
+    /// The difference (degrees) between the phase and the phase of the nearest form
+    pub fn distance_to_form(&self) -> f64 {
+        let nearest = (self.phase / STEP).round() * STEP;
+        (self.phase - nearest).abs()
+    }
+
+    /// The sector of the pseudorotation wheel
+    pub fn sector(&self) -> Sector {
+        Sector::from_phase(self.phase)
+    }
+
+    /// A ring without amplitude, of which the phase means nothing
+    pub fn is_planar(&self) -> bool {
+        self.amplitude.abs() < PLANAR_AMPLITUDE
+    }
+
+    /// The IUPAC label of the nearest form, or `planar`
+    pub fn label(&self) -> String {
+        if self.is_planar() {
+            "planar".to_string()
+        } else {
+            self.form().label()
+        }
+    }
+
+    /// The nucleoside label of the nearest form, or `planar`
+    pub fn description(&self) -> String {
+        if self.is_planar() {
+            "planar".to_string()
+        } else {
+            self.form().description()
+        }
+    }
+
+    /// The letter of the sector, or `-` for a planar ring
+    pub fn sector_letter(&self) -> &'static str {
+        if self.is_planar() {
+            "-"
+        } else {
+            self.sector().letter()
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::f64::consts::PI;
+
+use crate::arguments::Flags;
+use crate::ring::reconstruct::BondParameters;
+use crate::ring::{default_amplitude, prompted_bonds, ring_stream, RingStream};
+use crate::torsion_typing::Fourring;
+
+/// The largest puckering amplitude q2 to sample up to; prompted or the default for fourrings
+pub fn fourring_amplitude(flags: &Flags) -> f64 {
+    flags.amplitude.unwrap_or(default_amplitude(4))
+}
+
+/// The bond parameters of the fourring. A planar fourring is a square, so the bond angle
+/// starts out at 90 degrees. When the ring is rebuilt, the angle is derived from the pucker of
+/// every point (see `BondParameters::for_pucker`) and closes up as the ring puckers
+pub fn fourring_bonds(flags: &Flags) -> BondParameters {
+    prompted_bonds(flags, 4, PI / 2.)
+}
+
+/// Generate the torsion angles to use as restraints for four-membered rings
+///
+/// A four-membered ring (cyclobutane, azetidine, oxetane ...) has a single puckering
+/// coordinate q2, which is sampled in [-Q, Q]. The sign of q2 flips the ring, so q2 = 0 is the
+/// planar ring. The single torsion angle nu0 then runs monotonically along the axis.
+///
+/// The pipeline mirrors the one of the sixring :
+///     puckering axis -> local elevation -> reconstruction -> dihedral
+pub fn fourring(flags: &Flags) -> Fourring {
+    let fourrings = fourring_stream(flags);
+
+    let mut f = Fourring::new(fourrings.modes.len());
+
+    for (i, (modes, nu)) in fourrings.modes.iter().zip(&fourrings.torsions).enumerate() {
+        f.nu0[i] = nu[0];
+        f.q2[i] = modes.amplitudes[0];
+    }
+
+    // Dihedral function has values ORCA-ready
+    f
+}
+
+/// Generate the stream of torsion angles for four-membered rings
+pub fn fourring_stream(flags: &Flags) -> RingStream {
+    ring_stream(
+        4,
+        flags.num,
+        fourring_amplitude(flags),
+        &fourring_bonds(flags),
+    )
+}
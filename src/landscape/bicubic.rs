@@ -0,0 +1,342 @@
+/// Periodic bicubic interpolation of the energies on a peptide (phi, psi) grid.
+///
+/// The peptide grid spans [0, 360] on both axes, so the last row and column repeat the first
+/// ones. The surface is built the way CMAP surfaces are built in CHARMM :
+///     the derivatives at the grid points come from periodic cubic splines along both axes
+///     (the cross derivative is the spline of the phi-derivative along psi),
+///     every cell of the grid is then a bicubic Hermite patch through its four corners.
+/// The surface passes through every grid point, and its first derivatives are continuous
+/// across the borders of the cells, including the periodic border at 0 / 360 degrees.
+///
+/// For a cell with corners (i, j) .. (i+1, j+1), at fractions t, u of the cell :
+/// ```text
+///     E(t, u) = SUM_{a,b} [ f_ab H_a(t) H_b(u)  + h fx_ab  G_a(t) H_b(u)
+///                                               + h fy_ab  H_a(t) G_b(u)
+///                                               + h^2 fxy_ab G_a(t) G_b(u) ]
+///     H_0 = 2t^3 - 3t^2 + 1    H_1 = -2t^3 + 3t^2
+///     G_0 = t^3 - 2t^2 + t     G_1 = t^3 - t^2
+/// ```
+//
+// import modules
+use std::io::Write;
+
+use anyhow::{bail, Result};
+use nalgebra::{DMatrix, Dyn, LU};
+use ndarray::Array2;
+
+use crate::arguments::Flags;
+use crate::landscape::energies::relative_to_minimum;
+use crate::landscape::table::{read_table, LandscapeTable};
+use crate::torsion_typing::{PeptideAxes, TorsionType};
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The atom types on the CMAP line; the usual CHARMM backbone, to be replaced where needed
+const CMAP_ATOM_TYPES: &str = "C    NH1  CT1  C    NH1  CT1  C    NH1";
+
+/// The periodic bicubic surface over (phi, psi)
+/// public `values` field : Array2<f64>. E at (phi_i, psi_j) = (i h, j h), without the 360 row
+/// public `spacing` field : f64. The grid spacing h (degrees)
+pub struct PeriodicBicubic {
+    pub values: Array2<f64>,
+    pub spacing: f64,
+    dphi: Array2<f64>,
+    dpsi: Array2<f64>,
+    dphidpsi: Array2<f64>,
+}
+
+impl PeriodicBicubic {
+    /// Build the surface out of the values on a square periodic grid of spacing 360 / n
+    pub fn new(values: Array2<f64>) -> PeriodicBicubic {
+        let n = values.nrows();
+        let spacing = 360. / n as f64;
+        let spline = PeriodicSpline::new(n, spacing);
+
+        let dphi = spline
+            .derivatives_along_rows(&values.t().to_owned())
+            .t()
+            .to_owned();
+        let dpsi = spline.derivatives_along_rows(&values);
+        let dphidpsi = spline.derivatives_along_rows(&dphi);
+
+        PeriodicBicubic {
+            values,
+            spacing,
+            dphi,
+            dpsi,
+            dphidpsi,
+        }
+    }
+
+    /// The interpolated energy at any (phi, psi), in degrees
+    pub fn evaluate(&self, phi: f64, psi: f64) -> f64 {
+        let n = self.values.nrows();
+        let h = self.spacing;
+
+        let x = phi.rem_euclid(360.) / h;
+        let y = psi.rem_euclid(360.) / h;
+        let (t, u) = (x - x.floor(), y - y.floor());
+        let i = [x.floor() as usize % n, (x.floor() as usize + 1) % n];
+        let j = [y.floor() as usize % n, (y.floor() as usize + 1) % n];
+
+        let (ht, gt) = hermite(t);
+        let (hu, gu) = hermite(u);
+
+        let mut e = 0.;
+        for a in 0..2 {
+            for b in 0..2 {
+                let corner = [i[a], j[b]];
+                e += self.values[corner] * ht[a] * hu[b]
+                    + h * self.dphi[corner] * gt[a] * hu[b]
+                    + h * self.dpsi[corner] * ht[a] * gu[b]
+                    + h * h * self.dphidpsi[corner] * gt[a] * gu[b];
+            }
+        }
+        e
+    }
+}
+
+/// The Hermite basis functions ([H_0, H_1], [G_0, G_1]) at t
+fn hermite(t: f64) -> ([f64; 2], [f64; 2]) {
+    let (t2, t3) = (t * t, t * t * t);
+    (
+        [2. * t3 - 3. * t2 + 1., -2. * t3 + 3. * t2],
+        [t3 - 2. * t2 + t, t3 - t2],
+    )
+}
+
+/// Periodic cubic splines through n equidistant points.
+/// The second derivatives M of the spline solve the cyclic system, which is factorised once
+/// ```text
+///     M_{i-1} + 4 M_i + M_{i+1} = 6 (y_{i+1} - 2 y_i + y_{i-1}) / h^2
+///     y'_i = (y_{i+1} - y_i) / h - h (2 M_i + M_{i+1}) / 6
+/// ```
+struct PeriodicSpline {
+    n: usize,
+    h: f64,
+    lu: LU<f64, Dyn, Dyn>,
+}
+
+impl PeriodicSpline {
+    fn new(n: usize, h: f64) -> PeriodicSpline {
+        let mut a = DMatrix::<f64>::zeros(n, n);
+        for i in 0..n {
+            a[(i, i)] += 4.;
+            a[(i, (i + 1) % n)] += 1.;
+            a[(i, (i + n - 1) % n)] += 1.;
+        }
+        PeriodicSpline { n, h, lu: a.lu() }
+    }
+
+    /// The derivatives of the splines through every row of the array
+    fn derivatives_along_rows(&self, rows: &Array2<f64>) -> Array2<f64> {
+        let (n, h) = (self.n, self.h);
+        let y = |r: usize, i: usize| rows[[r, i % n]];
+
+        let mut rhs = DMatrix::<f64>::zeros(n, rows.nrows());
+        for r in 0..rows.nrows() {
+            for i in 0..n {
+                rhs[(i, r)] = 6. * (y(r, i + 1) - 2. * y(r, i) + y(r, i + n - 1)) / (h * h);
+            }
+        }
+        let m = self
+            .lu
+            .solve(&rhs)
+            .expect("the periodic spline system is not singular");
+
+        let mut derivatives = Array2::<f64>::zeros(rows.dim());
+        for r in 0..rows.nrows() {
+            for i in 0..n {
+                derivatives[[r, i]] =
+                    (y(r, i + 1) - y(r, i)) / h - h * (2. * m[(i, r)] + m[((i + 1) % n, r)]) / 6.;
+            }
+        }
+        derivatives
+    }
+}
+
+/// Gather the relative energies of a peptide landscape on its periodic grid
+pub fn peptide_surface(table: &LandscapeTable, flags: &Flags) -> Result<PeriodicBicubic> {
+    if table.grid() != Some(TorsionType::Peptide) {
+        bail!("The interpolation of (phi, psi) needs a landscape table of a peptide grid")
+    }
+
+    let energies = relative_to_minimum(table, flags.unit)?;
+    let phi = table.column("PHI").unwrap_or_default();
+    let psi = table.column("PSI").unwrap_or_default();
+
+    let phi_axis = grid_axis(&phi)?;
+    let psi_axis = grid_axis(&psi)?;
+    if phi_axis != psi_axis {
+        bail!("The peptide grid has a different spacing along phi and psi")
+    }
+
+    let n = phi_axis;
+    let h = 360. / n as f64;
+    let mut values = Array2::<f64>::from_elem((n, n), f64::NAN);
+    for k in 0..table.len() {
+        let i = ((phi[k].rem_euclid(360.) / h).round() as usize) % n;
+        let j = ((psi[k].rem_euclid(360.) / h).round() as usize) % n;
+        if values[[i, j]].is_nan() {
+            values[[i, j]] = energies[k];
+        }
+    }
+
+    let missing = values.iter().filter(|e| e.is_nan()).count();
+    if missing > 0 {
+        bail!(
+            "{} of the {} points of the peptide grid have no converged energy",
+            missing,
+            n * n
+        )
+    }
+
+    Ok(PeriodicBicubic::new(values))
+}
+
+/// The amount of distinct grid points along an axis in [0, 360), which need to be equidistant
+fn grid_axis(angles: &[f64]) -> Result<usize> {
+    let mut distinct: Vec<f64> = Vec::new();
+    for a in angles.iter().map(|a| a.rem_euclid(360.)) {
+        // 360 is the same grid point as 0
+        let a = if (a - 360.).abs() < 1e-3 { 0. } else { a };
+        if !distinct.iter().any(|d| (d - a).abs() < 1e-3) {
+            distinct.push(a);
+        }
+    }
+    distinct.sort_by(|a, b| a.total_cmp(b));
+
+    let n = distinct.len();
+    if n < 3 {
+        bail!("The peptide grid needs at least three points along phi and psi")
+    }
+    let h = 360. / n as f64;
+    if distinct
+        .iter()
+        .enumerate()
+        .any(|(k, a)| (a - k as f64 * h).abs() > 1e-2)
+    {
+        bail!("The peptide grid is not equidistant in [0, 360)")
+    }
+    Ok(n)
+}
+
+/// The interpolated energies at the prompted (phi, psi) points
+pub fn write_queries<W: Write>(
+    surface: &PeriodicBicubic,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    write_header(flags, writer)?;
+    for &[phi, psi] in &flags.query {
+        write_point(phi, psi, surface.evaluate(phi, psi), writer)?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The interpolated energies on a finer grid of `num` x `num` points (as --peptide `num`)
+pub fn write_resampled<W: Write>(
+    surface: &PeriodicBicubic,
+    num: u64,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let axes = PeptideAxes::new(num as usize);
+    write_header(flags, writer)?;
+    for &phi in axes.x.iter() {
+        for &psi in axes.y.iter() {
+            write_point(phi, psi, surface.evaluate(phi, psi), writer)?;
+        }
+    }
+    Ok(writer.flush()?)
+}
+
+fn write_header<W: Write>(flags: &Flags, writer: &mut W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>w$}",
+        "PHI",
+        "PSI",
+        flags.unit.column(),
+        w1 = WIDTH - 1,
+        w = WIDTH
+    )
+}
+
+fn write_point<W: Write>(phi: f64, psi: f64, e: f64, writer: &mut W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{:w$.p$} {:w$.p$} {:w$.p$}",
+        phi,
+        psi,
+        e,
+        w = WIDTH,
+        p = PRECISION
+    )
+}
+
+/// A CHARMM-style CMAP table of `num` x `num` points over [-180, 180), phi running slowest.
+/// With a second surface (e.g. the same scan with the force field), the table holds the
+/// difference between both, which is the CMAP correction
+pub fn write_cmap<W: Write>(
+    surface: &PeriodicBicubic,
+    subtract: Option<&PeriodicBicubic>,
+    num: u64,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let h = 360. / num as f64;
+    let correction = |phi: f64, psi: f64| match subtract {
+        Some(other) => surface.evaluate(phi, psi) - other.evaluate(phi, psi),
+        None => surface.evaluate(phi, psi),
+    };
+
+    writeln!(writer, "CMAP")?;
+    writeln!(
+        writer,
+        "! puckers : {} x {} grid, spacing {:.1} degrees, {}",
+        num,
+        num,
+        h,
+        flags.unit.column()
+    )?;
+    writeln!(writer, "{}  {}", CMAP_ATOM_TYPES, num)?;
+
+    for a in 0..num {
+        let phi = -180. + a as f64 * h;
+        writeln!(writer, "\n!phi = {:.1}", phi)?;
+
+        let values: Vec<String> = (0..num)
+            .map(|b| format!("{:10.6}", correction(phi, -180. + b as f64 * h)))
+            .collect();
+        for line in values.chunks(5) {
+            writeln!(writer, "{}", line.join(" "))?;
+        }
+    }
+    Ok(writer.flush()?)
+}
+
+/// Run the prompted interpolation of the peptide landscape table
+pub fn write_peptide_interpolation<W: Write>(
+    table: &LandscapeTable,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    if flags.fit.is_some() {
+        bail!("A peptide landscape is interpolated on its grid, --fit does not apply")
+    }
+    let surface = peptide_surface(table, flags)?;
+
+    if let Some(num) = flags.cmap {
+        let subtract = match &flags.subtract {
+            Some(other) => Some(peptide_surface(&read_table(other)?, flags)?),
+            None => None,
+        };
+        write_cmap(&surface, subtract.as_ref(), num, flags, writer)
+    } else if let Some(num) = flags.resample {
+        write_resampled(&surface, num, flags, writer)
+    } else {
+        write_queries(&surface, flags, writer)
+    }
+}
@@ -0,0 +1,273 @@
+/// Relative energies and Boltzmann populations over a landscape table.
+///
+/// Only the points with a converged job (STATUS `ok`) and a finite energy take part. The other
+/// points keep their line in the table, with NaN as relative energy and population.
+/// The relative energies are taken with respect to the global minimum, or to a chosen point of
+/// the grid (by its INDEX), and converted from Hartree into the prompted unit.
+///
+/// The populations always use the global minimum, to keep the exponentials from overflowing
+/// ```text
+///     p_i = exp( -(E_i - E_min) / (k_B * T) ) / SUM_j exp( -(E_j - E_min) / (k_B * T) )
+/// ```
+//
+// import modules
+use std::io::Write;
+
+use anyhow::{bail, Result};
+
+use crate::arguments::Flags;
+use crate::landscape::table::LandscapeTable;
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The populations are much smaller than the energies, so they get more decimals
+const POPULATION_PRECISION: usize = 6;
+
+/// The Boltzmann constant in Hartree per Kelvin
+pub const BOLTZMANN_HARTREE: f64 = 3.166811563e-6;
+
+/// The units the relative energies can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnergyUnit {
+    KcalMol,
+    KjMol,
+    Ev,
+    Hartree,
+}
+
+impl EnergyUnit {
+    /// Parse the name of a unit, as prompted on the command line
+    pub fn from_name(name: &str) -> Option<EnergyUnit> {
+        match name.to_lowercase().as_str() {
+            "kcal" | "kcal/mol" => Some(EnergyUnit::KcalMol),
+            "kj" | "kj/mol" => Some(EnergyUnit::KjMol),
+            "ev" => Some(EnergyUnit::Ev),
+            "hartree" | "eh" => Some(EnergyUnit::Hartree),
+            _ => None,
+        }
+    }
+
+    /// The value of one Hartree in this unit
+    pub fn per_hartree(&self) -> f64 {
+        match self {
+            EnergyUnit::KcalMol => 627.509474,
+            EnergyUnit::KjMol => 2625.499639,
+            EnergyUnit::Ev => 27.211386246,
+            EnergyUnit::Hartree => 1.,
+        }
+    }
+
+    /// The name of the column of relative energies
+    pub fn column(&self) -> &'static str {
+        match self {
+            EnergyUnit::KcalMol => "REL_KCAL",
+            EnergyUnit::KjMol => "REL_KJ",
+            EnergyUnit::Ev => "REL_EV",
+            EnergyUnit::Hartree => "REL_EH",
+        }
+    }
+}
+
+/// The energies (Hartree) of the points that take part, NaN for all others
+pub fn usable_energies(table: &LandscapeTable) -> Result<Vec<f64>> {
+    let energies = match table.column("ENERGY") {
+        Some(e) => e,
+        None => bail!("The landscape table has no ENERGY column"),
+    };
+
+    Ok(match table.text_column("STATUS") {
+        Some(status) => energies
+            .iter()
+            .zip(status)
+            .map(|(e, s)| if s == "ok" { *e } else { f64::NAN })
+            .collect(),
+        None => energies,
+    })
+}
+
+/// The row of the lowest energy
+pub fn global_minimum(energies: &[f64]) -> Option<usize> {
+    energies
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_finite())
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+}
+
+/// The energies relative to the energy of the reference row, in the prompted unit
+pub fn relative_energies(energies: &[f64], reference: usize, unit: EnergyUnit) -> Vec<f64> {
+    let e_ref = energies[reference];
+    energies
+        .iter()
+        .map(|e| (e - e_ref) * unit.per_hartree())
+        .collect()
+}
+
+/// The energies of a table relative to its global minimum, in the prompted unit.
+/// These are what the interpolations and fits work on
+pub fn relative_to_minimum(table: &LandscapeTable, unit: EnergyUnit) -> Result<Vec<f64>> {
+    let energies = usable_energies(table)?;
+    match global_minimum(&energies) {
+        Some(minimum) => Ok(relative_energies(&energies, minimum, unit)),
+        None => bail!("The landscape table has no converged energies"),
+    }
+}
+
+/// The rows with a converged energy. Only these points are fitted
+pub fn converged_rows(energies: &[f64]) -> Vec<usize> {
+    (0..energies.len())
+        .filter(|&i| energies[i].is_finite())
+        .collect()
+}
+
+/// The root mean square and the largest absolute residual of a fit
+pub fn residual_summary(residuals: &[f64]) -> (f64, f64) {
+    let n = residuals.len() as f64;
+    let rms = (residuals.iter().map(|r| r * r).sum::<f64>() / n).sqrt();
+    let max = residuals.iter().fold(0_f64, |m, r| m.max(r.abs()));
+    (rms, max)
+}
+
+/// Add the fitted and the residual energy as new columns behind the table.
+/// Both are given for the fitted `rows` only, all other points get NaN
+pub fn write_residuals<W: Write>(
+    table: &LandscapeTable,
+    rows: &[usize],
+    fitted: &[f64],
+    residuals: &[f64],
+    writer: &mut W,
+) -> Result<()> {
+    let mut fit_column = vec![f64::NAN; table.len()];
+    let mut resid_column = vec![f64::NAN; table.len()];
+    for (k, &i) in rows.iter().enumerate() {
+        fit_column[i] = fitted[k];
+        resid_column[i] = residuals[k];
+    }
+
+    writeln!(
+        writer,
+        "{} {:>w$} {:>w$}",
+        table.header,
+        "FIT",
+        "RESID",
+        w = WIDTH
+    )?;
+    for (i, row) in table.rows.iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {:w$.p$} {:w$.p$}",
+            row,
+            fit_column[i],
+            resid_column[i],
+            w = WIDTH,
+            p = PRECISION
+        )?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The Boltzmann populations at temperature T (Kelvin). These sum to one
+pub fn boltzmann_populations(energies: &[f64], temperature: f64) -> Vec<f64> {
+    let e_min = match global_minimum(energies) {
+        Some(i) => energies[i],
+        None => return vec![f64::NAN; energies.len()],
+    };
+
+    let kt = BOLTZMANN_HARTREE * temperature;
+    let weights: Vec<f64> = energies.iter().map(|e| (-(e - e_min) / kt).exp()).collect();
+    let total: f64 = weights.iter().filter(|w| w.is_finite()).sum();
+
+    weights.iter().map(|w| w / total).collect()
+}
+
+/// Add the relative energies (and the populations) as new columns behind the table
+pub fn write_relative<W: Write>(
+    table: &LandscapeTable,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let energies = usable_energies(table)?;
+    let minimum = match global_minimum(&energies) {
+        Some(i) => i,
+        None => bail!("The landscape table has no converged energies"),
+    };
+
+    let reference = match flags.reference {
+        Some(index) => reference_row(table, index, &energies)?,
+        None => minimum,
+    };
+
+    let relative = relative_energies(&energies, reference, flags.unit);
+    let populations = flags
+        .temperature
+        .map(|t| boltzmann_populations(&energies, t));
+
+    let mut header = format!("{} {:>w$}", table.header, flags.unit.column(), w = WIDTH);
+    if populations.is_some() {
+        header.push_str(&format!(" {:>w$}", "POP", w = WIDTH));
+    }
+    writeln!(writer, "{}", header)?;
+
+    for (i, row) in table.rows.iter().enumerate() {
+        let mut line = format!("{} {:w$.p$}", row, relative[i], w = WIDTH, p = PRECISION);
+        if let Some(pop) = &populations {
+            line.push_str(&format!(
+                " {:w$.p$}",
+                pop[i],
+                w = WIDTH,
+                p = POPULATION_PRECISION
+            ));
+        }
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()?;
+
+    report_minimum(table, minimum, &energies);
+    Ok(())
+}
+
+/// The row of the point with the given INDEX, which needs a usable energy
+fn reference_row(table: &LandscapeTable, index: usize, energies: &[f64]) -> Result<usize> {
+    let indices = match table.column("INDEX") {
+        Some(i) => i,
+        None => bail!("The landscape table has no INDEX column to find the reference in"),
+    };
+
+    match indices.iter().position(|i| *i == index as f64) {
+        Some(row) if energies[row].is_finite() => Ok(row),
+        Some(_) => bail!("The reference point {} has no converged energy", index),
+        None => bail!(
+            "The reference point {} is not in the landscape table",
+            index
+        ),
+    }
+}
+
+/// Report where the global minimum lies on the grid to stderr
+fn report_minimum(table: &LandscapeTable, minimum: usize, energies: &[f64]) {
+    let usable = energies.iter().filter(|e| e.is_finite()).count();
+    let mut position = String::new();
+    for name in table.grid_columns() {
+        if let Some(values) = table.column(name) {
+            position.push_str(&format!(" {} {:.3}", name, values[minimum]));
+        }
+    }
+
+    if position.is_empty() {
+        position = format!(" line {}", minimum + 1);
+    }
+
+    let grid = match table.grid() {
+        Some(grid) => format!("{:?}", grid).to_lowercase(),
+        None => "unknown".to_string(),
+    };
+    eprintln!(
+        "Landscape of a {} grid : {} of {} points converged. Global minimum at{}",
+        grid,
+        usable,
+        table.len(),
+        position
+    );
+}
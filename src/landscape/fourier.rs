@@ -0,0 +1,333 @@
+/// Analytic models of the energies of a fivering landscape along the pseudorotation wheel.
+///
+/// Every point (Zx, Zy) of the grid is converted to its phase P and amplitude nu_max
+/// (see `fivering/pseudorotation.rs`). The energy is then fitted, by least squares, with a Fourier
+/// series in the phase whose coefficients are polynomials in the amplitude (degrees)
+/// ```text
+///     E(P, A) = SUM_j=0..M  A^j * ( a_j0 + SUM_k=1..min(j,K) ( a_jk cos(kP) + b_jk sin(kP) ) )
+/// ```
+/// The phase is undefined at the centre of the grid. Harmonic k therefore only comes with the
+/// powers j >= k of the amplitude, so every term vanishes at A = 0 but a_j0, and the surface is
+/// continuous at the planar ring. `fourier:K,M` (K <= M) thus holds
+/// (M + 1) + K * (2M - K + 1) coefficients, which can be written out to compare with the
+/// pseudorotation terms of a force field.
+//
+// import modules
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::{bail, Context, Result};
+use nalgebra::{DMatrix, DVector};
+
+use crate::arguments::Flags;
+use crate::fivering::pseudorotation::Pseudorotation;
+use crate::landscape::energies::{
+    converged_rows, relative_to_minimum, residual_summary, write_residuals,
+};
+use crate::landscape::table::LandscapeTable;
+use crate::landscape::SurfaceFit;
+use crate::torsion_typing::{FuranoseAxes, TorsionType};
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The coefficients of the higher powers of the amplitude are small, so they are written
+/// in scientific notation
+const COEFFICIENT_WIDTH: usize = 16;
+const COEFFICIENT_PRECISION: usize = 8;
+
+/// The default model, when no --fit is prompted
+const DEFAULT_FIT: SurfaceFit = SurfaceFit::Fourier(3, 3);
+
+/// A Fourier/polynomial surface fitted over the pseudorotation wheel
+/// public `harmonics` field : usize. K, the highest harmonic of the phase
+/// public `degree` field : usize. M, the highest power of the amplitude
+/// public `coefficients` field : Vec<f64>. Ordered by power j, then a_j0, a_j1, b_j1, .. b_jmin(j,K)
+/// public `residuals` field : Vec<f64>. Per fitted point
+pub struct FourierSurface {
+    pub harmonics: usize,
+    pub degree: usize,
+    pub coefficients: Vec<f64>,
+    pub residuals: Vec<f64>,
+}
+
+impl FourierSurface {
+    /// Fit the values at the points (P, amplitude) (degrees)
+    pub fn new(
+        points: &[[f64; 2]],
+        values: &[f64],
+        harmonics: usize,
+        degree: usize,
+    ) -> Result<FourierSurface> {
+        if harmonics > degree {
+            bail!(
+                "Harmonic {} of the phase only comes with amplitudes from power {} on, not up to power {}",
+                harmonics,
+                harmonics,
+                degree
+            )
+        }
+
+        let amount = coefficient_count(harmonics, degree);
+        if points.len() < amount {
+            bail!(
+                "A Fourier series up to harmonic {} with amplitudes up to power {} needs at least {} points, not {}",
+                harmonics,
+                degree,
+                amount,
+                points.len()
+            )
+        }
+
+        let basis: Vec<Vec<f64>> = points
+            .iter()
+            .map(|p| fourier_basis(harmonics, degree, p[0], p[1]))
+            .collect();
+        let a = DMatrix::from_fn(points.len(), amount, |i, k| basis[i][k]);
+        let b = DVector::from_column_slice(values);
+        let c = match a.clone().svd(true, true).solve(&b, 1e-12) {
+            Ok(c) => c,
+            Err(e) => bail!("The Fourier fit failed : {}", e),
+        };
+
+        Ok(FourierSurface {
+            harmonics,
+            degree,
+            coefficients: c.iter().copied().collect(),
+            residuals: (&b - &a * &c).iter().copied().collect(),
+        })
+    }
+
+    /// The fitted value at any (P, amplitude) (degrees)
+    pub fn evaluate(&self, phase: f64, amplitude: f64) -> f64 {
+        fourier_basis(self.harmonics, self.degree, phase, amplitude)
+            .iter()
+            .zip(&self.coefficients)
+            .map(|(f, c)| f * c)
+            .sum()
+    }
+
+    /// The coefficients a_jk and b_jk (zero for k = 0), per power j and harmonic k <= j
+    pub fn terms(&self) -> Vec<(usize, usize, f64, f64)> {
+        let mut terms = Vec::with_capacity((self.degree + 1) * (self.harmonics + 1));
+        let mut c = self.coefficients.iter().copied();
+        for j in 0..=self.degree {
+            terms.push((j, 0, c.next().unwrap_or_default(), 0.));
+            for k in 1..=self.harmonics.min(j) {
+                let (a, b) = (c.next().unwrap_or_default(), c.next().unwrap_or_default());
+                terms.push((j, k, a, b));
+            }
+        }
+        terms
+    }
+
+    /// The root mean square and the largest absolute residual
+    pub fn residual_summary(&self) -> (f64, f64) {
+        residual_summary(&self.residuals)
+    }
+}
+
+/// The amount of coefficients of the model; one a_j0 for every power j, and a_jk, b_jk for
+/// every harmonic k <= min(j, K)
+pub fn coefficient_count(harmonics: usize, degree: usize) -> usize {
+    (0..=degree).map(|j| 1 + 2 * harmonics.min(j)).sum()
+}
+
+/// The functions A^j, A^j cos(kP), A^j sin(kP) (k <= j) of the model, in the order of the
+/// coefficients
+pub fn fourier_basis(harmonics: usize, degree: usize, phase: f64, amplitude: f64) -> Vec<f64> {
+    let phase = phase.to_radians();
+    let mut f = Vec::with_capacity(coefficient_count(harmonics, degree));
+
+    for j in 0..=degree {
+        let power = amplitude.powi(j as i32);
+        f.push(power);
+        for k in 1..=harmonics.min(j) {
+            let kp = k as f64 * phase;
+            f.push(power * kp.cos());
+            f.push(power * kp.sin());
+        }
+    }
+    f
+}
+
+/// Fit the relative energies of a fivering landscape over the pseudorotation wheel
+pub fn fivering_surface(
+    table: &LandscapeTable,
+    flags: &Flags,
+) -> Result<(FourierSurface, Vec<usize>)> {
+    if table.grid() != Some(TorsionType::Fivering) {
+        bail!("The Fourier fit needs a landscape table of a fivering grid")
+    }
+
+    let (harmonics, degree) = match flags.fit.unwrap_or(DEFAULT_FIT) {
+        SurfaceFit::Fourier(harmonics, degree) => (harmonics, degree),
+        fit => bail!("A fivering landscape can not be fitted with {}", fit.name()),
+    };
+
+    let energies = relative_to_minimum(table, flags.unit)?;
+    let zx = table.column("Zx").unwrap_or_default();
+    let zy = table.column("Zy").unwrap_or_default();
+
+    let rows = converged_rows(&energies);
+    let points: Vec<[f64; 2]> = rows
+        .iter()
+        .map(|&i| {
+            let p = Pseudorotation::from_zx_zy(zx[i], zy[i]);
+            [p.phase, p.amplitude]
+        })
+        .collect();
+    let values: Vec<f64> = rows.iter().map(|&i| energies[i]).collect();
+
+    let surface = FourierSurface::new(&points, &values, harmonics, degree)?;
+    Ok((surface, rows))
+}
+
+/// The coefficients of the fit, one line per power of the amplitude and harmonic of the phase
+pub fn write_coefficients<W: Write>(
+    surface: &FourierSurface,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "# E(P, A) in {} = SUM A^j ( a_jk cos(kP) + b_jk sin(kP) ), k <= j, with P and A in degrees",
+        flags.unit.column()
+    )?;
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>cw$} {:>cw$}",
+        "POWER",
+        "HARMONIC",
+        "COS",
+        "SIN",
+        w1 = WIDTH - 1,
+        w = WIDTH,
+        cw = COEFFICIENT_WIDTH
+    )?;
+    for (j, k, a, b) in surface.terms() {
+        writeln!(
+            writer,
+            "{:w$} {:w$} {:cw$.cp$e} {:cw$.cp$e}",
+            j,
+            k,
+            a,
+            b,
+            w = WIDTH,
+            cw = COEFFICIENT_WIDTH,
+            cp = COEFFICIENT_PRECISION
+        )?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The fitted energies at the prompted (P, amplitude) points (degrees)
+pub fn write_queries<W: Write>(
+    surface: &FourierSurface,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>w$}",
+        "P",
+        "AMPLITUDE",
+        flags.unit.column(),
+        w1 = WIDTH - 1,
+        w = WIDTH
+    )?;
+    for &[phase, amplitude] in &flags.query {
+        writeln!(
+            writer,
+            "{:w$.p$} {:w$.p$} {:w$.p$}",
+            phase,
+            amplitude,
+            surface.evaluate(phase, amplitude),
+            w = WIDTH,
+            p = PRECISION
+        )?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The fitted energies on a new NUM x NUM (Zx, Zy) grid (as --fivering `num`)
+pub fn write_resampled<W: Write>(
+    surface: &FourierSurface,
+    num: u64,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>w$} {:>w$} {:>w$}",
+        "Zx",
+        "Zy",
+        "P",
+        "AMPLITUDE",
+        flags.unit.column(),
+        w1 = WIDTH - 1,
+        w = WIDTH
+    )?;
+    let axes = FuranoseAxes::new(num as usize);
+    for &zx in axes.zx.iter() {
+        for &zy in axes.zy.iter() {
+            let p = Pseudorotation::from_zx_zy(zx, zy);
+            writeln!(
+                writer,
+                "{:w$.p$} {:w$.p$} {:w$.p$} {:w$.p$} {:w$.p$}",
+                zx,
+                zy,
+                p.phase,
+                p.amplitude,
+                surface.evaluate(p.phase, p.amplitude),
+                w = WIDTH,
+                p = PRECISION
+            )?;
+        }
+    }
+    Ok(writer.flush()?)
+}
+
+/// Run the prompted fit of the fivering landscape table, write its coefficients out
+/// when asked for and report the residuals of the fit to stderr
+pub fn write_fivering_fit<W: Write>(
+    table: &LandscapeTable,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let (surface, rows) = fivering_surface(table, flags)?;
+
+    let (rms, max) = surface.residual_summary();
+    eprintln!(
+        "Fitted {} points with a Fourier series up to harmonic {} and amplitude power {} : residuals RMS {:.3}, largest {:.3} ({})",
+        rows.len(),
+        surface.harmonics,
+        surface.degree,
+        rms,
+        max,
+        flags.unit.column()
+    );
+
+    if let Some(path) = &flags.coefficients {
+        let file = File::create(path)
+            .with_context(|| format!("Could not write the coefficients to `{}`", path))?;
+        write_coefficients(&surface, flags, &mut BufWriter::new(file))?;
+    }
+
+    if let Some(num) = flags.resample {
+        write_resampled(&surface, num, flags, writer)
+    } else if !flags.query.is_empty() {
+        write_queries(&surface, flags, writer)
+    } else {
+        let zx = table.column("Zx").unwrap_or_default();
+        let zy = table.column("Zy").unwrap_or_default();
+        let fitted: Vec<f64> = rows
+            .iter()
+            .map(|&i| {
+                let p = Pseudorotation::from_zx_zy(zx[i], zy[i]);
+                surface.evaluate(p.phase, p.amplitude)
+            })
+            .collect();
+        write_residuals(table, &rows, &fitted, &surface.residuals, writer)
+    }
+}
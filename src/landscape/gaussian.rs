@@ -0,0 +1,166 @@
+/// Read the results of a finished Gaussian job out of its `.log` file.
+///
+/// Looked for are :
+///     the last `SCF Done` energy (Hartree),
+///     `Normal termination` or `Error termination`, `Optimization completed` or `stopped`,
+///     the final values of the dihedrals that were frozen in the ModRedundant section.
+///
+/// The frozen dihedrals are echoed in the ModRedundant section at the start of the log, and
+/// their values are read from the last table of parameters, which is the `Optimized Parameters`
+/// table once the optimization completed.
+/// ```text
+///  The following ModRedundant input section has been read:
+///  D       1       2       3       4 F
+///  ...
+///  ! D1    D(1,2,3,4)            -60.0012         -DE/DX =    0.0                 !
+/// ```
+//
+// import modules
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::landscape::{find_output, JobResult, JobStatus, OutputParser};
+
+/// The banner every Gaussian log file carries
+const GAUSSIAN_BANNER: &str = "Entering Gaussian System";
+
+/// Reads the `.log` file of a Gaussian job
+pub struct Gaussian;
+
+impl OutputParser for Gaussian {
+    fn engine(&self) -> &'static str {
+        "Gaussian"
+    }
+
+    fn read_job(&self, directory: &Path) -> JobResult {
+        match find_output(directory, "log", GAUSSIAN_BANNER)
+            .and_then(|p| fs::read_to_string(p).ok())
+        {
+            Some(text) => parse_gaussian(&text),
+            None => JobResult::missing(self.engine()),
+        }
+    }
+}
+
+/// Parse the content of a Gaussian log file
+pub fn parse_gaussian(text: &str) -> JobResult {
+    let mut energy = None;
+    let mut normal = false;
+    let mut error = false;
+    let mut optimization = false;
+    let mut completed = false;
+    let mut scf_failed = false;
+
+    // the atoms of the frozen dihedrals, in the order of the ModRedundant section
+    let mut frozen: Vec<[usize; 4]> = Vec::new();
+    let mut in_modredundant = false;
+    let mut table: HashMap<String, f64> = HashMap::new();
+
+    for line in text.lines() {
+        // the section ends at the first line that is not a frozen dihedral, which is read as usual
+        if in_modredundant {
+            match frozen_dihedral(line) {
+                Some(atoms) => {
+                    frozen.push(atoms);
+                    continue;
+                }
+                None => in_modredundant = false,
+            }
+        }
+
+        if line.contains("The following ModRedundant input section has been read") {
+            in_modredundant = true;
+        } else if line.contains("SCF Done:") {
+            energy = line
+                .split('=')
+                .nth(1)
+                .and_then(|e| e.split_whitespace().next())
+                .and_then(|e| e.parse::<f64>().ok());
+        } else if line.contains("Normal termination of Gaussian") {
+            normal = true;
+        } else if line.contains("Error termination") {
+            error = true;
+        } else if line.contains("Berny optimization") {
+            optimization = true;
+        } else if line.contains("Optimization completed") {
+            completed = true;
+        } else if line.contains("Convergence failure") {
+            scf_failed = true;
+        } else if line.contains("! Name") && line.contains("Definition") {
+            // a new table of parameters starts
+            table.clear();
+        } else if let Some((definition, value)) = parameter(line) {
+            table.insert(definition, value);
+        }
+    }
+
+    // the table may list the dihedral the other way around
+    let dihedrals = frozen
+        .iter()
+        .map_while(|&[a, b, c, d]| {
+            table
+                .get(&definition([a, b, c, d]))
+                .or_else(|| table.get(&definition([d, c, b, a])))
+                .copied()
+        })
+        .collect();
+
+    let (status, reason) = if error {
+        (
+            JobStatus::Crashed,
+            Some("Gaussian ended in an error termination"),
+        )
+    } else if !normal {
+        (
+            JobStatus::Crashed,
+            Some("Gaussian did not terminate normally"),
+        )
+    } else if scf_failed {
+        (JobStatus::Unconverged, Some("the SCF did not converge"))
+    } else if optimization && !completed {
+        (
+            JobStatus::Unconverged,
+            Some("the optimization did not complete"),
+        )
+    } else {
+        (JobStatus::Converged, None)
+    };
+
+    JobResult {
+        status,
+        energy,
+        dihedrals,
+        reason: reason.map(String::from),
+    }
+}
+
+/// The atoms of a frozen dihedral in the ModRedundant section
+fn frozen_dihedral(line: &str) -> Option<[usize; 4]> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 6 || fields[0] != "D" || fields[fields.len() - 1] != "F" {
+        return None;
+    }
+
+    let mut atoms = [0_usize; 4];
+    for (k, field) in fields[1..5].iter().enumerate() {
+        atoms[k] = field.parse::<usize>().ok()?;
+    }
+    Some(atoms)
+}
+
+/// How a dihedral is named in the tables of parameters
+fn definition(atoms: [usize; 4]) -> String {
+    format!("D({},{},{},{})", atoms[0], atoms[1], atoms[2], atoms[3])
+}
+
+/// A dihedral in a table of parameters, and its value
+fn parameter(line: &str) -> Option<(String, f64)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 4 || fields[0] != "!" || !fields[2].starts_with("D(") {
+        return None;
+    }
+
+    let value = fields[3].parse::<f64>().ok()?;
+    Some((fields[2].to_string(), value))
+}
@@ -0,0 +1,135 @@
+/// The neighbours of every point of a sampled landscape.
+///
+/// The peptide and fivering grids are regular, so every grid point has the eight points around it
+/// as neighbours. The peptide grid wraps around at 0 / 360 degrees; the fivering grid does not,
+/// so the points on its border are not surrounded by their neighbours (the ring is open).
+///
+/// The points on the sixring sphere lie irregularly. Their neighbours are those of the Gabriel
+/// graph, the edges of the nearest-neighbour (Delaunay) triangulation whose diametral sphere holds
+/// no other point
+/// ```text
+///     i - j are neighbours  <=>  |p_i - p_k|^2 + |p_j - p_k|^2 >= |p_i - p_j|^2  for every k
+/// ```
+/// Only the nearest candidates of every point are checked, since any point that blocks an edge
+/// lies closer than both of its ends.
+///
+/// The neighbours of every point are ordered around it (counterclockwise), so the energies can be
+/// followed along the ring of neighbours.
+//
+// import modules
+use std::f64::consts::PI;
+
+use crate::parallel::map_indices;
+use crate::sixring::geometry::{tangent_basis, unit_vector, Coordinate};
+
+/// The amount of nearest points that are checked as neighbours on the sphere
+const CANDIDATES: usize = 16;
+
+/// The offsets (row, column) of the eight neighbours on a grid, counterclockwise
+const GRID_OFFSETS: [(i64, i64); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// The neighbours of every point
+/// public `neighbours` field : Vec<Vec<usize>>. Ordered around the point
+/// public `closed` field : Vec<bool>. Whether the neighbours surround the point
+pub struct NeighbourGraph {
+    pub neighbours: Vec<Vec<usize>>,
+    pub closed: Vec<bool>,
+}
+
+impl NeighbourGraph {
+    /// The graph of a rows x columns grid, where point (r, c) is `r * columns + c`
+    pub fn grid(rows: usize, columns: usize, periodic: bool) -> NeighbourGraph {
+        let mut neighbours = Vec::with_capacity(rows * columns);
+        let mut closed = Vec::with_capacity(rows * columns);
+
+        for r in 0..rows as i64 {
+            for c in 0..columns as i64 {
+                let around: Vec<usize> = GRID_OFFSETS
+                    .iter()
+                    .filter_map(|(dr, dc)| {
+                        let (mut nr, mut nc) = (r + dr, c + dc);
+                        if periodic {
+                            nr = nr.rem_euclid(rows as i64);
+                            nc = nc.rem_euclid(columns as i64);
+                        }
+                        if (0..rows as i64).contains(&nr) && (0..columns as i64).contains(&nc) {
+                            Some((nr * columns as i64 + nc) as usize)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                closed.push(around.len() == GRID_OFFSETS.len());
+                neighbours.push(around);
+            }
+        }
+
+        NeighbourGraph { neighbours, closed }
+    }
+
+    /// The Gabriel graph of the points (theta, phi) (radians) on the sphere
+    pub fn sphere(points: &[[f64; 2]]) -> NeighbourGraph {
+        let xyz: Vec<Coordinate> = points.iter().map(|p| unit_vector(p[0], p[1])).collect();
+        let d2 =
+            |a: usize, b: usize| -> f64 { (0..3).map(|k| (xyz[a][k] - xyz[b][k]).powi(2)).sum() };
+
+        let gabriel: Vec<Vec<usize>> = map_indices(points.len(), |i| {
+            let mut nearest: Vec<usize> = (0..points.len()).filter(|&j| j != i).collect();
+            if nearest.len() > CANDIDATES {
+                nearest.select_nth_unstable_by(CANDIDATES, |&a, &b| d2(i, a).total_cmp(&d2(i, b)));
+                nearest.truncate(CANDIDATES);
+            }
+
+            nearest
+                .iter()
+                .copied()
+                .filter(|&j| {
+                    let dij = d2(i, j);
+                    !nearest.iter().any(|&k| k != j && d2(i, k) + d2(j, k) < dij)
+                })
+                .collect()
+        });
+
+        // only the nearest candidates were checked, so an edge may be known by one end only
+        let mut neighbours = gabriel.clone();
+        for (i, around) in gabriel.iter().enumerate() {
+            for &j in around {
+                if !neighbours[j].contains(&i) {
+                    neighbours[j].push(i);
+                }
+            }
+        }
+
+        for (i, around) in neighbours.iter_mut().enumerate() {
+            let [e1, e2] = tangent_basis(points[i][0], points[i][1]);
+            let azimuth = |j: &usize| -> f64 {
+                let dot = |e: &Coordinate| (0..3).map(|k| xyz[*j][k] * e[k]).sum::<f64>();
+                dot(&e2).atan2(dot(&e1)).rem_euclid(2. * PI)
+            };
+            around.sort_by(|a, b| azimuth(a).total_cmp(&azimuth(b)));
+        }
+
+        NeighbourGraph {
+            closed: vec![true; points.len()],
+            neighbours,
+        }
+    }
+
+    /// The amount of points
+    pub fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighbours.is_empty()
+    }
+}
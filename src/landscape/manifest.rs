@@ -0,0 +1,69 @@
+/// The job manifest links every point of a sampling space to the directory its QM job ran in.
+///
+/// It is a plain text file with one job per line : the index of the point in the table that
+/// puckers printed (starting at 0), followed by the directory of the job.
+/// Empty lines and lines starting with a `#` are skipped.
+/// Relative directories are taken relative to the directory of the manifest itself.
+/// ```text
+/// # INDEX  DIRECTORY
+/// 0        jobs/conf_0000
+/// 1        jobs/conf_0001
+/// ```
+//
+// import modules
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+/// A single job of the manifest
+/// public `index` field : usize. The index of the point in the sampling space
+/// public `directory` field : PathBuf. The directory (or output file) of the job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub index: usize,
+    pub directory: PathBuf,
+}
+
+/// Read the manifest from a file
+pub fn read_manifest(path: &str) -> Result<Vec<Job>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Could not read the manifest `{}`", path))?;
+
+    let root = Path::new(path).parent().unwrap_or(Path::new(""));
+    parse_manifest(&text, root)
+}
+
+/// Parse the content of a manifest, with relative directories taken from `root`
+pub fn parse_manifest(text: &str, root: &Path) -> Result<Vec<Job>> {
+    let mut jobs = Vec::new();
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let (index, directory) = match (fields.next(), fields.next()) {
+            (Some(i), Some(d)) => (i, d),
+            _ => bail!("Line {} of the manifest is not `INDEX DIRECTORY`", n + 1),
+        };
+
+        let index = match index.parse::<usize>() {
+            Ok(i) => i,
+            Err(_) => bail!(
+                "Line {} of the manifest has no valid index `{}`",
+                n + 1,
+                index
+            ),
+        };
+
+        jobs.push(Job {
+            index,
+            directory: root.join(directory),
+        });
+    }
+
+    Ok(jobs)
+}
@@ -0,0 +1,387 @@
+/// Collect the results of the QM jobs that ran on the points of a sampling space
+/// into a single landscape table.
+///
+/// Every QM engine (ORCA, Gaussian, xtb) has its own parser, behind the `OutputParser` trait.
+/// A parser reads the directory of a job and hands out a `JobResult`, with the reason why the job
+/// did not end well (if so), so crashed and unconverged jobs can be reported one by one.
+///
+/// The sampling space is generated again out of the same flags that produced the jobs, so every
+/// job of the manifest (see `manifest.rs`) can be joined with the torsion angles and the
+/// coordinates of its point (phi/psi, Zx/Zy, theta/phi, ...).
+/// The table has the same layout as the regular output (see `output.rs`), with the index of the
+/// point in front and the energy (Hartree), the status of the job and the final values of the
+/// constrained dihedrals behind it.
+/// ```text
+/// #    INDEX        PHI        PSI          X          Y           ENERGY     STATUS    OPT_PHI    OPT_PSI
+///          0      0.000      0.000      0.000      0.000    -495.12345678         ok      0.000      0.000
+/// ```
+//
+// import modules
+pub mod bicubic;
+pub mod energies;
+pub mod fourier;
+pub mod gaussian;
+pub mod graph;
+pub mod manifest;
+pub mod orca;
+pub mod path;
+pub mod spherical;
+pub mod stationary;
+pub mod table;
+pub mod xtb;
+
+use std::fs;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+
+use crate::arguments::Flags;
+use crate::landscape::bicubic::write_peptide_interpolation;
+use crate::landscape::energies::write_relative;
+use crate::landscape::fourier::write_fivering_fit;
+use crate::landscape::gaussian::Gaussian;
+use crate::landscape::manifest::{read_manifest, Job};
+use crate::landscape::orca::Orca;
+use crate::landscape::path::write_path;
+use crate::landscape::spherical::write_sphere_interpolation;
+use crate::landscape::stationary::write_stationary;
+use crate::landscape::table::read_table;
+use crate::landscape::xtb::Xtb;
+use crate::output::{format_sample, header};
+use crate::parallel::map_indices;
+use crate::plot::{render, write_svg, PlotPoints};
+use crate::torsion_typing::{SamplingSpace, TorsionType};
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+const ENERGY_WIDTH: usize = 16;
+const ENERGY_PRECISION: usize = 8;
+
+/// How a QM job ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Converged,
+    Unconverged,
+    Crashed,
+    Missing,
+}
+
+impl JobStatus {
+    /// The short name in the STATUS column
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobStatus::Converged => "ok",
+            JobStatus::Unconverged => "unconv",
+            JobStatus::Crashed => "crashed",
+            JobStatus::Missing => "missing",
+        }
+    }
+}
+
+/// The results of a single QM job
+/// public `status` field : JobStatus
+/// public `energy` field : Option<f64>. The final energy (Hartree)
+/// public `dihedrals` field : Vec<f64>. The final values of the constrained dihedrals (degrees)
+/// public `reason` field : Option<String>. Why the job did not converge, if so
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobResult {
+    pub status: JobStatus,
+    pub energy: Option<f64>,
+    pub dihedrals: Vec<f64>,
+    pub reason: Option<String>,
+}
+
+impl JobResult {
+    /// A job of which no output could be found
+    pub fn missing(engine: &str) -> JobResult {
+        JobResult {
+            status: JobStatus::Missing,
+            energy: None,
+            dihedrals: vec![],
+            reason: Some(format!("no {} output found", engine)),
+        }
+    }
+}
+
+/// Read the results of a finished job, whichever QM engine ran it
+pub trait OutputParser: Sync {
+    /// The name of the QM engine, used when reporting on the jobs
+    fn engine(&self) -> &'static str;
+
+    /// Read the results of the job that ran in `directory`.
+    /// The manifest may also point to the output file itself
+    fn read_job(&self, directory: &Path) -> JobResult;
+}
+
+/// The QM engines that can be collected from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QmEngine {
+    Orca,
+    Gaussian,
+    Xtb,
+}
+
+impl QmEngine {
+    /// Parse the name of a QM engine, as prompted on the command line
+    pub fn from_name(name: &str) -> Option<QmEngine> {
+        match name.to_lowercase().as_str() {
+            "orca" => Some(QmEngine::Orca),
+            "gaussian" | "g16" | "g09" => Some(QmEngine::Gaussian),
+            "xtb" => Some(QmEngine::Xtb),
+            _ => None,
+        }
+    }
+
+    /// The parser of the output files of this engine
+    pub fn parser(&self) -> &'static dyn OutputParser {
+        match self {
+            QmEngine::Orca => &Orca,
+            QmEngine::Gaussian => &Gaussian,
+            QmEngine::Xtb => &Xtb,
+        }
+    }
+}
+
+/// The output file of a job : either the path in the manifest, when it is a file,
+/// or the first file in the directory with the given extension that carries the banner
+pub fn find_output(directory: &Path, extension: &str, banner: &str) -> Option<PathBuf> {
+    if directory.is_file() {
+        return Some(directory.to_path_buf());
+    }
+
+    let mut candidates: Vec<PathBuf> = fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect();
+    candidates.sort();
+
+    candidates
+        .into_iter()
+        .find(|path| fs::read_to_string(path).is_ok_and(|text| text.contains(banner)))
+}
+
+/// Read the results of all jobs of the manifest, in the order of the manifest
+pub fn collect_jobs(jobs: &[Job], parser: &dyn OutputParser) -> Vec<JobResult> {
+    map_indices(jobs.len(), |k| parser.read_job(&jobs[k].directory))
+}
+
+/// The header line of the landscape table
+pub fn landscape_header(space: &dyn SamplingSpace, flags: &Flags) -> String {
+    // the pound symbol moves to the INDEX column
+    let mut line = format!("#{:>width$} ", "INDEX", width = WIDTH - 1);
+    line.push_str(&header(space, flags).replacen('#', " ", 1));
+
+    line.push_str(&format!(
+        " {:>ew$} {:>w$}",
+        "ENERGY",
+        "STATUS",
+        ew = ENERGY_WIDTH,
+        w = WIDTH
+    ));
+    for name in space.torsion_names() {
+        line.push_str(&format!(" {:>w$}", format!("OPT_{}", name), w = WIDTH));
+    }
+    line
+}
+
+/// A single line of the landscape table.
+/// Missing values are written as NaN, which numpy and most plotting tools understand
+pub fn format_job(
+    space: &dyn SamplingSpace,
+    job: &Job,
+    result: &JobResult,
+    flags: &Flags,
+) -> String {
+    let mut line = format!("{:>w$} ", job.index, w = WIDTH);
+    line.push_str(&format_sample(
+        &space.sample(job.index, flags.labels),
+        flags,
+    ));
+
+    line.push_str(&format!(
+        " {:ew$.ep$} {:>w$}",
+        result.energy.unwrap_or(f64::NAN),
+        result.status.name(),
+        ew = ENERGY_WIDTH,
+        ep = ENERGY_PRECISION,
+        w = WIDTH
+    ));
+    for k in 0..space.torsion_names().len() {
+        let value = result.dihedrals.get(k).copied().unwrap_or(f64::NAN);
+        line.push_str(&format!(" {:w$.p$}", value, w = WIDTH, p = PRECISION));
+    }
+    line
+}
+
+/// Collect all jobs of the manifest and write the landscape table to any writer
+pub fn write_landscape<W: Write>(
+    space: &dyn SamplingSpace,
+    jobs: &[Job],
+    results: &[JobResult],
+    flags: &Flags,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writeln!(writer, "{}", landscape_header(space, flags))?;
+    for (job, result) in jobs.iter().zip(results) {
+        writeln!(writer, "{}", format_job(space, job, result, flags))?;
+    }
+    writer.flush()
+}
+
+/// Collect the jobs of the manifest, print the landscape table to stdout
+/// and every job that did not converge, together with a summary of the statuses, to stderr
+pub fn print_landscape(space: &dyn SamplingSpace, manifest: &str, flags: &Flags) -> Result<()> {
+    let jobs = read_manifest(manifest)?;
+    if let Some(job) = jobs.iter().find(|job| job.index >= space.len()) {
+        bail!(
+            "Job index {} is out of the sampling space ({} points)",
+            job.index,
+            space.len()
+        )
+    }
+
+    let results = collect_jobs(&jobs, flags.engine.parser());
+
+    let stdout = std::io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    match write_landscape(space, &jobs, &results, flags, &mut writer) {
+        Ok(_) => (),
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => (),
+        Err(e) => return Err(e.into()),
+    }
+
+    for (job, result) in jobs.iter().zip(&results) {
+        if let Some(reason) = &result.reason {
+            eprintln!(
+                "Job {} ({}) : {} : {}",
+                job.index,
+                job.directory.display(),
+                result.status.name(),
+                reason
+            );
+        }
+    }
+
+    let count = |status: JobStatus| results.iter().filter(|r| r.status == status).count();
+    eprintln!(
+        "Collected {} job(s) : {} converged, {} unconverged, {} crashed, {} missing",
+        results.len(),
+        count(JobStatus::Converged),
+        count(JobStatus::Unconverged),
+        count(JobStatus::Crashed),
+        count(JobStatus::Missing)
+    );
+    Ok(())
+}
+
+/// The models a landscape can be fitted with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurfaceFit {
+    /// real spherical harmonics up to a degree L (sixring)
+    Harmonics(usize),
+    /// radial basis functions exp(-d / EPS) of the geodesic distance (sixring)
+    Rbf(f64),
+    /// a Fourier series in the phase up to harmonic K, with coefficients polynomial
+    /// in the amplitude up to power M (fivering)
+    Fourier(usize, usize),
+}
+
+impl SurfaceFit {
+    /// Parse a model as prompted on the command line, e.g. `harmonics:6`, `rbf:0.5` or `fourier:3,3`
+    pub fn from_query(query: &str) -> Result<SurfaceFit, String> {
+        let (kind, value) = match query.split_once(':') {
+            Some(kv) => kv,
+            None => return Err(format!("`{}` is not formatted as KIND:VALUE", query)),
+        };
+
+        match kind.to_lowercase().as_str() {
+            "harmonics" => match value.parse::<usize>() {
+                Ok(degree) => Ok(SurfaceFit::Harmonics(degree)),
+                Err(_) => Err(format!("`{}` not parsed as a degree", value)),
+            },
+            "rbf" => match value.parse::<f64>() {
+                Ok(epsilon) if epsilon > 0. => Ok(SurfaceFit::Rbf(epsilon)),
+                _ => Err(format!("`{}` not parsed as a positive EPS", value)),
+            },
+            "fourier" => match value.split_once(',').map(|(k, m)| (k.parse(), m.parse())) {
+                Some((Ok(harmonics), Ok(degree))) => Ok(SurfaceFit::Fourier(harmonics, degree)),
+                _ => Err(format!(
+                    "`{}` not parsed as a harmonic and a power `K,M`",
+                    value
+                )),
+            },
+            _ => Err(format!(
+                "`{}` is not a known model (harmonics, rbf, fourier)",
+                kind
+            )),
+        }
+    }
+
+    /// The name of the model, when reporting on the fit
+    pub fn name(&self) -> String {
+        match self {
+            SurfaceFit::Harmonics(degree) => format!("spherical harmonics up to degree {}", degree),
+            SurfaceFit::Rbf(epsilon) => {
+                format!("geodesic radial basis functions (EPS {})", epsilon)
+            }
+            SurfaceFit::Fourier(harmonics, degree) => format!(
+                "a Fourier series up to harmonic {} and amplitude power {}",
+                harmonics, degree
+            ),
+        }
+    }
+}
+
+/// Post-process a landscape table : find its stationary points, the path between two
+/// conformers or interpolate (or fit) it when asked for, or else add the relative energies
+/// behind every point
+pub fn process_landscape(path: &str, flags: &Flags) -> Result<()> {
+    let table = read_table(path)?;
+    let interpolate = !flags.query.is_empty()
+        || flags.resample.is_some()
+        || flags.cmap.is_some()
+        || flags.fit.is_some()
+        || flags.coefficients.is_some();
+
+    if flags.coefficients.is_some() && table.grid() != Some(TorsionType::Fivering) {
+        bail!("Only the Fourier fit of a fivering landscape has --coefficients to write out")
+    }
+
+    if let Some(image) = &flags.svg {
+        let points = PlotPoints::from_landscape(&table, flags.unit)?;
+        write_svg(
+            image,
+            &render(&points, flags.projection, flags.unit.column()),
+        )?;
+    }
+
+    let stdout = std::io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    let written = if flags.stationary {
+        write_stationary(&table, flags, &mut writer)
+    } else if let Some(ends) = flags.path {
+        write_path(&table, ends, flags, &mut writer)
+    } else {
+        match (interpolate, table.grid()) {
+            (false, _) => write_relative(&table, flags, &mut writer),
+            (true, Some(TorsionType::Peptide)) => {
+                write_peptide_interpolation(&table, flags, &mut writer)
+            }
+            (true, Some(TorsionType::Fivering)) => write_fivering_fit(&table, flags, &mut writer),
+            (true, Some(TorsionType::Sixring)) => {
+                write_sphere_interpolation(&table, flags, &mut writer)
+            }
+            (true, _) => bail!("The landscape table has no grid that can be interpolated"),
+        }
+    };
+
+    match written {
+        Ok(_) => Ok(()),
+        Err(e) => match e.downcast_ref::<std::io::Error>() {
+            Some(io) if io.kind() == ErrorKind::BrokenPipe => Ok(()),
+            _ => Err(e),
+        },
+    }
+}
@@ -0,0 +1,107 @@
+/// Read the results of a finished ORCA job out of its output file.
+///
+/// Only three things are looked for :
+///     the last `FINAL SINGLE POINT ENERGY` (Hartree),
+///     whether the job terminated normally and the optimization converged,
+///     the final values of the constrained dihedrals.
+///
+/// The constrained dihedrals are read from the last table of internal coordinates, which is the
+/// `--- Optimized Parameters ---` table once the optimization converged. Constrained coordinates
+/// are marked with a trailing `C` and their final value is the column right before it.
+/// ```text
+///     35. D(C   5,C   4,C   3,C   2)    -50.1234  0.000000  0.0000  -50.1234 C
+/// ```
+//
+// import modules
+use std::fs;
+use std::path::Path;
+
+use crate::landscape::{find_output, JobResult, JobStatus, OutputParser};
+
+/// The banner every ORCA output file carries
+const ORCA_BANNER: &str = "* O   R   C   A *";
+
+/// Reads the `.out` file of an ORCA job
+pub struct Orca;
+
+impl OutputParser for Orca {
+    fn engine(&self) -> &'static str {
+        "ORCA"
+    }
+
+    fn read_job(&self, directory: &Path) -> JobResult {
+        match find_output(directory, "out", ORCA_BANNER).and_then(|p| fs::read_to_string(p).ok()) {
+            Some(text) => parse_orca(&text),
+            None => JobResult::missing(self.engine()),
+        }
+    }
+}
+
+/// Parse the content of an ORCA output file
+pub fn parse_orca(text: &str) -> JobResult {
+    let mut energy = None;
+    let mut terminated = false;
+    let mut optimization = false;
+    let mut converged = false;
+    let mut scf_failed = false;
+    let mut dihedrals: Vec<f64> = Vec::new();
+    let mut table: Vec<f64> = Vec::new();
+
+    for line in text.lines() {
+        if line.contains("FINAL SINGLE POINT ENERGY") {
+            energy = line
+                .split_whitespace()
+                .last()
+                .and_then(|e| e.parse::<f64>().ok());
+        } else if line.contains("ORCA TERMINATED NORMALLY") {
+            terminated = true;
+        } else if line.contains("Geometry Optimization Run") {
+            optimization = true;
+        } else if line.contains("THE OPTIMIZATION HAS CONVERGED") {
+            converged = true;
+        } else if line.contains("SCF NOT CONVERGED") {
+            scf_failed = true;
+        } else if line.contains("Definition") {
+            // a new table of internal coordinates starts
+            if !table.is_empty() {
+                dihedrals = std::mem::take(&mut table);
+            }
+        } else if let Some(value) = constrained_dihedral(line) {
+            table.push(value);
+        }
+    }
+    if !table.is_empty() {
+        dihedrals = table;
+    }
+
+    let (status, reason) = if !terminated {
+        (JobStatus::Crashed, Some("ORCA did not terminate normally"))
+    } else if scf_failed {
+        (JobStatus::Unconverged, Some("the SCF did not converge"))
+    } else if optimization && !converged {
+        (
+            JobStatus::Unconverged,
+            Some("the optimization did not converge"),
+        )
+    } else {
+        (JobStatus::Converged, None)
+    };
+
+    JobResult {
+        status,
+        energy,
+        dihedrals,
+        reason: reason.map(String::from),
+    }
+}
+
+/// The final value of a constrained dihedral in a table of internal coordinates
+fn constrained_dihedral(line: &str) -> Option<f64> {
+    let line = line.trim();
+    if !line.contains(". D(") || !line.ends_with(" C") {
+        return None;
+    }
+
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    fields[fields.len() - 2].parse::<f64>().ok()
+}
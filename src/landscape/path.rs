@@ -0,0 +1,344 @@
+/// The path of the lowest barrier between two conformations on the sixring sphere.
+///
+/// Both ends (a conformer label, or a point (theta, phi) in degrees) are placed on the nearest
+/// converged point of the landscape, and the path runs along the neighbour graph of the sphere
+/// (see `graph.rs`). It is found in two rounds of Dijkstra :
+///     the lowest barrier B that connects both ends (minimax : the highest energy along a path
+///     is what it costs),
+///     the shortest path (great-circle length) over the edges that stay at or below B.
+/// The height of an edge is the highest energy of its two ends. With `--refine`, the sphere is
+/// fitted first (see `spherical.rs`) and the edges are followed on the fitted surface as well,
+/// so the profile of the path holds the energies in between the grid points.
+/// ```text
+///     barrier (forward)  = B - E(from)
+///     barrier (backward) = B - E(to)
+/// ```
+//
+// import modules
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::io::Write;
+
+use anyhow::{bail, Result};
+
+use crate::arguments::Flags;
+use crate::landscape::energies::relative_to_minimum;
+use crate::landscape::graph::NeighbourGraph;
+use crate::landscape::spherical::{geodesic, sixring_surface, SphereSurface};
+use crate::landscape::stationary::landscape_graph;
+use crate::landscape::table::LandscapeTable;
+use crate::parallel::map_indices;
+use crate::sixring::conformers::{classify, find_conformer};
+use crate::sixring::geometry::great_circle;
+use crate::torsion_typing::TorsionType;
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The points in between the grid points at which every edge is followed on the fitted surface
+const EDGE_SAMPLES: usize = 8;
+
+/// Parse the ends of a path `FROM:TO`, where either end is a conformer label (e.g. `4C1`)
+/// or a point `THETA,PHI` in degrees
+pub fn parse_path(query: &str) -> Result<[[f64; 2]; 2], String> {
+    match query.split_once(':') {
+        Some((from, to)) => Ok([parse_end(from)?, parse_end(to)?]),
+        None => Err(format!("`{}` is not formatted as FROM:TO", query)),
+    }
+}
+
+/// A point (theta, phi) in degrees, or the position of a conformer
+fn parse_end(end: &str) -> Result<[f64; 2], String> {
+    if let [theta, phi] = end.split(',').collect::<Vec<&str>>()[..] {
+        if let (Ok(theta), Ok(phi)) = (theta.trim().parse::<f64>(), phi.trim().parse::<f64>()) {
+            return Ok([theta, phi]);
+        }
+    }
+
+    match find_conformer(end.trim()) {
+        Some(conformer) => Ok([conformer.theta, conformer.phi]),
+        None => Err(format!(
+            "`{}` is neither a point `THETA,PHI` nor a known conformer",
+            end
+        )),
+    }
+}
+
+/// A point along the path
+/// public `row` field : Option<usize>. The line of the grid point in the table, None in between
+/// public `position` field : [f64; 2]. (theta, phi) in radians
+/// public `energy` field : f64. Relative to the global minimum
+#[derive(Debug, Clone, Copy)]
+pub struct PathPoint {
+    pub row: Option<usize>,
+    pub position: [f64; 2],
+    pub energy: f64,
+}
+
+/// The path of the lowest barrier
+/// public `profile` field : Vec<PathPoint>. From the first to the last end
+/// public `barrier` field : f64. The highest energy along the path
+/// public `length` field : f64. The great-circle length of the path, in radians
+pub struct EnergyPath {
+    pub profile: Vec<PathPoint>,
+    pub barrier: f64,
+    pub length: f64,
+}
+
+impl EnergyPath {
+    /// The point of the path with the highest energy
+    pub fn highest(&self) -> &PathPoint {
+        self.profile
+            .iter()
+            .max_by(|a, b| a.energy.total_cmp(&b.energy))
+            .unwrap_or(&self.profile[0])
+    }
+
+    /// The barriers from the first end (forward) and from the last end (backward)
+    pub fn barriers(&self) -> (f64, f64) {
+        let first = self.profile.first().map_or(f64::NAN, |p| p.energy);
+        let last = self.profile.last().map_or(f64::NAN, |p| p.energy);
+        (self.barrier - first, self.barrier - last)
+    }
+}
+
+/// A point to visit in Dijkstra, with the lowest cost first
+#[derive(PartialEq)]
+struct Visit {
+    cost: f64,
+    node: usize,
+}
+
+impl Eq for Visit {}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Dijkstra from `start` (at cost `initial`) on, where following an edge costs
+/// `step(cost so far, u, v)`. Returns the cost of and the previous node on the cheapest path
+/// to every node
+fn dijkstra<F>(
+    graph: &NeighbourGraph,
+    start: usize,
+    initial: f64,
+    step: F,
+) -> (Vec<f64>, Vec<Option<usize>>)
+where
+    F: Fn(f64, usize, usize) -> Option<f64>,
+{
+    let mut cost = vec![f64::INFINITY; graph.len()];
+    let mut previous = vec![None; graph.len()];
+    let mut queue = BinaryHeap::new();
+
+    cost[start] = initial;
+    queue.push(Visit {
+        cost: initial,
+        node: start,
+    });
+    while let Some(Visit { cost: c, node: u }) = queue.pop() {
+        if c > cost[u] {
+            continue;
+        }
+        for &v in &graph.neighbours[u] {
+            if let Some(next) = step(c, u, v) {
+                if next < cost[v] {
+                    cost[v] = next;
+                    previous[v] = Some(u);
+                    queue.push(Visit {
+                        cost: next,
+                        node: v,
+                    });
+                }
+            }
+        }
+    }
+
+    (cost, previous)
+}
+
+/// The path of the lowest barrier between the two ends (theta, phi) (degrees)
+pub fn minimum_energy_path(
+    table: &LandscapeTable,
+    ends: [[f64; 2]; 2],
+    flags: &Flags,
+) -> Result<EnergyPath> {
+    if table.grid() != Some(TorsionType::Sixring) {
+        bail!("The path between conformers needs a landscape table of a sixring grid")
+    }
+
+    let energies = relative_to_minimum(table, flags.unit)?;
+    let (graph, nodes) = landscape_graph(table)?;
+    let theta = table.column("THETA").unwrap_or_default();
+    let phi = table.column("PHI").unwrap_or_default();
+    let rows: Vec<usize> = nodes.iter().flatten().copied().collect();
+    let position = |v: usize| [theta[rows[v]], phi[rows[v]]];
+    let energy = |v: usize| energies[rows[v]];
+
+    let surface: Option<SphereSurface> = if flags.refine {
+        Some(sixring_surface(table, flags)?.0)
+    } else {
+        None
+    };
+    // the points in between two grid points, on the fitted surface
+    let in_between = |u: usize, v: usize| -> Vec<PathPoint> {
+        match &surface {
+            Some(surface) => (1..=EDGE_SAMPLES)
+                .map(|k| {
+                    let t = k as f64 / (EDGE_SAMPLES + 1) as f64;
+                    let [theta, phi] = great_circle(position(u), position(v), t);
+                    PathPoint {
+                        row: None,
+                        position: [theta, phi],
+                        energy: surface.evaluate(theta, phi),
+                    }
+                })
+                .collect(),
+            None => vec![],
+        }
+    };
+    // the height of every edge, in the order of the neighbours
+    let heights: Vec<Vec<f64>> = map_indices(graph.len(), |u| {
+        graph.neighbours[u]
+            .iter()
+            .map(|&v| {
+                in_between(u, v)
+                    .iter()
+                    .fold(energy(u).max(energy(v)), |h, p| h.max(p.energy))
+            })
+            .collect()
+    });
+    let height = |u: usize, v: usize| match graph.neighbours[u].iter().position(|&n| n == v) {
+        Some(k) => heights[u][k],
+        None => f64::INFINITY,
+    };
+
+    let nearest = |end: [f64; 2]| {
+        let end = [end[0].to_radians(), end[1].to_radians()];
+        (0..rows.len())
+            .min_by(|&a, &b| geodesic(end, position(a)).total_cmp(&geodesic(end, position(b))))
+    };
+    let (start, goal) = match (nearest(ends[0]), nearest(ends[1])) {
+        (Some(start), Some(goal)) if start != goal => (start, goal),
+        (Some(_), Some(_)) => bail!("Both ends of the path lie on the same point of the grid"),
+        _ => bail!("The landscape table has no converged points"),
+    };
+
+    // the lowest barrier that connects both ends
+    let (barriers, _) = dijkstra(&graph, start, energy(start), |c, u, v| {
+        Some(c.max(height(u, v)))
+    });
+    let barrier = barriers[goal];
+    if !barrier.is_finite() {
+        bail!("The ends of the path are not connected on the sphere")
+    }
+
+    // the shortest path below that barrier
+    let (lengths, previous) = dijkstra(&graph, start, 0., |c, u, v| {
+        if height(u, v) <= barrier + 1e-9 {
+            Some(c + geodesic(position(u), position(v)))
+        } else {
+            None
+        }
+    });
+
+    let mut nodes_along = vec![goal];
+    while let Some(u) = previous[*nodes_along.last().unwrap_or(&start)] {
+        nodes_along.push(u);
+    }
+    nodes_along.reverse();
+
+    let mut profile = Vec::new();
+    for (k, &v) in nodes_along.iter().enumerate() {
+        if k > 0 {
+            profile.extend(in_between(nodes_along[k - 1], v));
+        }
+        profile.push(PathPoint {
+            row: Some(rows[v]),
+            position: position(v),
+            energy: energy(v),
+        });
+    }
+
+    Ok(EnergyPath {
+        profile,
+        barrier,
+        length: lengths[goal],
+    })
+}
+
+/// Write the profile of the path of the lowest barrier, and report the barrier to stderr
+pub fn write_path<W: Write>(
+    table: &LandscapeTable,
+    ends: [[f64; 2]; 2],
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let path = minimum_energy_path(table, ends, flags)?;
+    let indices = table.text_column("INDEX");
+    let index = |row: Option<usize>| match (row, &indices) {
+        (Some(r), Some(i)) => i[r].to_string(),
+        (Some(r), None) => r.to_string(),
+        (None, _) => "-".to_string(),
+    };
+    let label = |p: &PathPoint| {
+        classify(p.position[0], p.position[1])
+            .conformer
+            .label(&flags.ring_atoms)
+    };
+
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>w$} {:>w$} {:>w$} {:>w$}",
+        "STEP",
+        "INDEX",
+        "THETA",
+        "PHI",
+        flags.unit.column(),
+        "LABEL",
+        w1 = WIDTH - 1,
+        w = WIDTH
+    )?;
+    for (step, p) in path.profile.iter().enumerate() {
+        writeln!(
+            writer,
+            "{:w$} {:>w$} {:w$.p$} {:w$.p$} {:w$.p$} {:>w$}",
+            step,
+            index(p.row),
+            p.position[0],
+            p.position[1],
+            p.energy,
+            label(p),
+            w = WIDTH,
+            p = PRECISION
+        )?;
+    }
+    writer.flush()?;
+
+    let (first, last) = (&path.profile[0], &path.profile[path.profile.len() - 1]);
+    let highest = path.highest();
+    let (forward, backward) = path.barriers();
+    eprintln!(
+        "Path from {} ({}) to {} ({}) over {} grid points and {:.1} degrees : highest point at INDEX {} ({}), barrier {:.3} forward and {:.3} backward ({})",
+        index(first.row),
+        label(first),
+        index(last.row),
+        label(last),
+        path.profile.iter().filter(|p| p.row.is_some()).count(),
+        path.length.to_degrees(),
+        index(highest.row),
+        label(highest),
+        forward,
+        backward,
+        flags.unit.column()
+    );
+    Ok(())
+}
@@ -0,0 +1,294 @@
+/// Smooth interpolation of the energies on the puckering sphere of sixrings.
+///
+/// The sampled points (theta, phi) lie irregularly on the sphere, so the energies are fitted
+/// with functions that live on the sphere itself. Both models are linear in their coefficients :
+///     harmonics:L : a least-squares fit of the real spherical harmonics Y_lm up to degree L,
+///                   which needs at least (L + 1)^2 points,
+///     rbf:EPS     : radial basis functions centred on every point, with the geodesic distance d
+///                   (radians) and the exponential kernel exp(-d / EPS), which stays positive
+///                   definite on the sphere. It passes through every point.
+///
+/// The residuals of the harmonics are those of the fit. Since the radial basis functions pass
+/// through every point, their residuals are the leave-one-out errors (Rippa's formula) instead
+/// ```text
+///     e_i = E_i - E_fit_{without i}(p_i) = c_i / (K^-1)_ii
+/// ```
+//
+// import modules
+use std::f64::consts::PI;
+use std::io::Write;
+
+use anyhow::{bail, Result};
+use nalgebra::{DMatrix, DVector};
+
+use crate::arguments::Flags;
+use crate::landscape::energies::{
+    converged_rows, relative_to_minimum, residual_summary, write_residuals,
+};
+use crate::landscape::table::LandscapeTable;
+use crate::landscape::SurfaceFit;
+use crate::torsion_typing::TorsionType;
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The default model, when no --fit is prompted
+const DEFAULT_FIT: SurfaceFit = SurfaceFit::Harmonics(6);
+
+/// A surface fitted on the sphere
+/// public `fit` field : SurfaceFit. The model that was fitted
+/// public `coefficients` field : Vec<f64>
+/// public `residuals` field : Vec<f64>. Per fitted point (see above)
+pub struct SphereSurface {
+    pub fit: SurfaceFit,
+    pub coefficients: Vec<f64>,
+    pub residuals: Vec<f64>,
+    centres: Vec<[f64; 2]>,
+}
+
+impl SphereSurface {
+    /// Fit the values at the points (theta, phi) (radians)
+    pub fn new(points: &[[f64; 2]], values: &[f64], fit: SurfaceFit) -> Result<SphereSurface> {
+        let b = DVector::from_column_slice(values);
+
+        match fit {
+            SurfaceFit::Harmonics(degree) => {
+                let amount = (degree + 1).pow(2);
+                if points.len() < amount {
+                    bail!(
+                        "Spherical harmonics up to degree {} need at least {} points, not {}",
+                        degree,
+                        amount,
+                        points.len()
+                    )
+                }
+                let basis: Vec<Vec<f64>> = points
+                    .iter()
+                    .map(|p| real_harmonics(degree, p[0], p[1]))
+                    .collect();
+                let a = DMatrix::from_fn(points.len(), amount, |i, k| basis[i][k]);
+                let c = match a.clone().svd(true, true).solve(&b, 1e-12) {
+                    Ok(c) => c,
+                    Err(e) => bail!("The spherical harmonics fit failed : {}", e),
+                };
+                let residuals = (&b - &a * &c).iter().copied().collect();
+
+                Ok(SphereSurface {
+                    fit,
+                    coefficients: c.iter().copied().collect(),
+                    residuals,
+                    centres: vec![],
+                })
+            }
+            SurfaceFit::Rbf(epsilon) => {
+                let k = DMatrix::from_fn(points.len(), points.len(), |i, j| {
+                    (-geodesic(points[i], points[j]) / epsilon).exp()
+                });
+                let inverse = match k.try_inverse() {
+                    Some(inverse) => inverse,
+                    None => bail!("The radial basis functions can not be fitted, try another EPS"),
+                };
+                let c = &inverse * &b;
+                let residuals = (0..points.len()).map(|i| c[i] / inverse[(i, i)]).collect();
+
+                Ok(SphereSurface {
+                    fit,
+                    coefficients: c.iter().copied().collect(),
+                    residuals,
+                    centres: points.to_vec(),
+                })
+            }
+            SurfaceFit::Fourier(..) => bail!("{} is not a model on the sphere", fit.name()),
+        }
+    }
+
+    /// The fitted value at any (theta, phi) (radians)
+    pub fn evaluate(&self, theta: f64, phi: f64) -> f64 {
+        match self.fit {
+            SurfaceFit::Rbf(epsilon) => self
+                .centres
+                .iter()
+                .zip(&self.coefficients)
+                .map(|(centre, c)| c * (-geodesic(*centre, [theta, phi]) / epsilon).exp())
+                .sum(),
+            SurfaceFit::Harmonics(degree) => real_harmonics(degree, theta, phi)
+                .iter()
+                .zip(&self.coefficients)
+                .map(|(y, c)| y * c)
+                .sum(),
+            // never fitted on the sphere
+            SurfaceFit::Fourier(..) => f64::NAN,
+        }
+    }
+
+    /// The root mean square and the largest absolute residual
+    pub fn residual_summary(&self) -> (f64, f64) {
+        residual_summary(&self.residuals)
+    }
+}
+
+/// The angle (radians) between two points (theta, phi) on the sphere
+pub fn geodesic(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let cos = a[0].cos() * b[0].cos() + a[0].sin() * b[0].sin() * (a[1] - b[1]).cos();
+    cos.clamp(-1., 1.).acos()
+}
+
+/// All orthonormal real spherical harmonics up to `degree`, at (theta, phi).
+/// Ordered by degree l, and by order m = -l .. l within a degree
+/// ```text
+///     Y_l0 = N_l0 P_l0(cos theta)
+///     Y_lm = sqrt(2) N_lm P_lm(cos theta) cos(m phi)      m > 0
+///     Y_lm = sqrt(2) N_l|m| P_l|m|(cos theta) sin(|m| phi) m < 0
+///     N_lm = sqrt( (2l + 1) / 4 PI * (l - m)! / (l + m)! )
+/// ```
+pub fn real_harmonics(degree: usize, theta: f64, phi: f64) -> Vec<f64> {
+    let p = legendre(degree, theta.cos());
+    let mut y = Vec::with_capacity((degree + 1).pow(2));
+
+    for (l, pl) in p.iter().enumerate() {
+        for m in -(l as i64)..=(l as i64) {
+            let a = m.unsigned_abs() as usize;
+            // (l - a)! / (l + a)!
+            let ratio: f64 = ((l - a + 1)..=(l + a)).map(|k| 1. / k as f64).product();
+            let norm = ((2 * l + 1) as f64 / (4. * PI) * ratio).sqrt();
+
+            y.push(match m {
+                0 => norm * pl[0],
+                m if m > 0 => 2_f64.sqrt() * norm * pl[a] * (a as f64 * phi).cos(),
+                _ => 2_f64.sqrt() * norm * pl[a] * (a as f64 * phi).sin(),
+            });
+        }
+    }
+    y
+}
+
+/// The associated Legendre functions P_lm(x), for 0 <= m <= l <= degree
+fn legendre(degree: usize, x: f64) -> Vec<Vec<f64>> {
+    let mut p = vec![vec![0_f64; degree + 1]; degree + 1];
+    let s = (1. - x * x).max(0.).sqrt();
+
+    for m in 0..=degree {
+        // P_mm = (2m - 1)!! (1 - x^2)^(m/2)
+        p[m][m] = (1..=m).fold(1., |acc, k| acc * (2 * k - 1) as f64 * s);
+        if m < degree {
+            p[m + 1][m] = x * (2 * m + 1) as f64 * p[m][m];
+        }
+        for l in (m + 2)..=degree {
+            p[l][m] = ((2 * l - 1) as f64 * x * p[l - 1][m] - (l + m - 1) as f64 * p[l - 2][m])
+                / (l - m) as f64;
+        }
+    }
+    p
+}
+
+/// Fit the relative energies of a sixring landscape on the sphere
+pub fn sixring_surface(
+    table: &LandscapeTable,
+    flags: &Flags,
+) -> Result<(SphereSurface, Vec<usize>)> {
+    if table.grid() != Some(TorsionType::Sixring) {
+        bail!("The interpolation on the sphere needs a landscape table of a sixring grid")
+    }
+
+    let energies = relative_to_minimum(table, flags.unit)?;
+    let theta = table.column("THETA").unwrap_or_default();
+    let phi = table.column("PHI").unwrap_or_default();
+
+    let rows = converged_rows(&energies);
+    let points: Vec<[f64; 2]> = rows.iter().map(|&i| [theta[i], phi[i]]).collect();
+    let values: Vec<f64> = rows.iter().map(|&i| energies[i]).collect();
+
+    let surface = SphereSurface::new(&points, &values, flags.fit.unwrap_or(DEFAULT_FIT))?;
+    Ok((surface, rows))
+}
+
+/// The fitted energies at the prompted (theta, phi) points (degrees, as --region)
+pub fn write_queries<W: Write>(
+    surface: &SphereSurface,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    write_header(flags, writer)?;
+    for &[theta, phi] in &flags.query {
+        let (theta, phi) = (theta.to_radians(), phi.to_radians());
+        write_point(theta, phi, surface.evaluate(theta, phi), writer)?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// The fitted energies on a new sphere of approximately `num` points (as --sixring `num`)
+pub fn write_resampled<W: Write>(
+    surface: &SphereSurface,
+    num: u64,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    write_header(flags, writer)?;
+    for point in flags.sphere.generate(num).iter() {
+        write_point(
+            point.theta,
+            point.phi,
+            surface.evaluate(point.theta, point.phi),
+            writer,
+        )?;
+    }
+    Ok(writer.flush()?)
+}
+
+fn write_header<W: Write>(flags: &Flags, writer: &mut W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "#{:>w1$} {:>w$} {:>w$}",
+        "THETA",
+        "PHI",
+        flags.unit.column(),
+        w1 = WIDTH - 1,
+        w = WIDTH
+    )
+}
+
+fn write_point<W: Write>(theta: f64, phi: f64, e: f64, writer: &mut W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{:w$.p$} {:w$.p$} {:w$.p$}",
+        theta,
+        phi,
+        e,
+        w = WIDTH,
+        p = PRECISION
+    )
+}
+
+/// Run the prompted interpolation of the sixring landscape table
+/// and report the residuals of the fit to stderr
+pub fn write_sphere_interpolation<W: Write>(
+    table: &LandscapeTable,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let (surface, rows) = sixring_surface(table, flags)?;
+
+    let (rms, max) = surface.residual_summary();
+    eprintln!(
+        "Fitted {} points with {} : residuals RMS {:.3}, largest {:.3} ({})",
+        rows.len(),
+        surface.fit.name(),
+        rms,
+        max,
+        flags.unit.column()
+    );
+
+    if let Some(num) = flags.resample {
+        write_resampled(&surface, num, flags, writer)
+    } else if !flags.query.is_empty() {
+        write_queries(&surface, flags, writer)
+    } else {
+        let theta = table.column("THETA").unwrap_or_default();
+        let phi = table.column("PHI").unwrap_or_default();
+        let fitted: Vec<f64> = rows
+            .iter()
+            .map(|&i| surface.evaluate(theta[i], phi[i]))
+            .collect();
+        write_residuals(table, &rows, &fitted, &surface.residuals, writer)
+    }
+}
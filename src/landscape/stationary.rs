@@ -0,0 +1,439 @@
+/// Find the local minima and first-order saddle points of a sampled landscape.
+///
+/// Every point is compared with its neighbours (see `graph.rs`), followed around the point.
+/// A neighbour is either higher (+) or lower (-) than the point; equal energies are settled by
+/// the order of the points, so flat regions do not give a run of minima :
+///     no lower neighbour                        -> local minimum
+///     four sign changes around a closed ring    -> first-order saddle point (-, +, -, +)
+/// Points next to a point without a converged energy, and the points on the open border of the
+/// fivering grid, are never reported.
+///
+/// With `--refine`, every point is refined on the interpolated (or fitted) surface with Newton
+/// steps, from the grid point on. A refined point has to stay within two grid spacings of the grid
+/// point and keep its kind (both curvatures positive for a minimum, one negative for a saddle),
+/// else it is written as NaN.
+/// ```text
+///     x_{n+1} = x_n - H^-1 g      (steps no longer than half a grid spacing)
+/// ```
+/// On the sphere, the steps are taken in the plane tangent to the grid point, which also holds
+/// at the poles.
+//
+// import modules
+use std::io::Write;
+
+use anyhow::{bail, Result};
+
+use crate::arguments::Flags;
+use crate::fivering::pseudorotation::Pseudorotation;
+use crate::landscape::bicubic::peptide_surface;
+use crate::landscape::energies::{relative_to_minimum, usable_energies};
+use crate::landscape::fourier::fivering_surface;
+use crate::landscape::graph::NeighbourGraph;
+use crate::landscape::spherical::{geodesic, sixring_surface};
+use crate::landscape::table::LandscapeTable;
+use crate::sixring::conformers::classify;
+use crate::sixring::geometry::{tangent_basis, unit_vector};
+use crate::torsion_typing::TorsionType;
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The most Newton steps taken to refine a point
+const MAX_ITERATIONS: usize = 100;
+
+/// The kinds of stationary points that are looked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stationary {
+    Minimum,
+    Saddle,
+}
+
+impl Stationary {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stationary::Minimum => "minimum",
+            Stationary::Saddle => "saddle",
+        }
+    }
+}
+
+/// A stationary point of the landscape
+/// public `kind` field : Stationary
+/// public `row` field : usize. The line of the grid point in the table
+/// public `energy` field : f64. Relative to the global minimum
+/// public `refined` field : Option<([f64; 2], f64)>. The refined position and energy
+#[derive(Debug, Clone, Copy)]
+pub struct StationaryPoint {
+    pub kind: Stationary,
+    pub row: usize,
+    pub energy: f64,
+    pub refined: Option<([f64; 2], f64)>,
+}
+
+/// The kind of stationary point `i` is, if any, given the energies of all points
+pub fn classify_point(graph: &NeighbourGraph, energies: &[f64], i: usize) -> Option<Stationary> {
+    let around = &graph.neighbours[i];
+    if energies[i].is_nan() || around.is_empty() || !graph.closed[i] {
+        return None;
+    }
+    if around.iter().any(|&j| energies[j].is_nan()) {
+        return None;
+    }
+
+    let higher: Vec<bool> = around
+        .iter()
+        .map(|&j| (energies[j], j) > (energies[i], i))
+        .collect();
+
+    if higher.iter().all(|&h| h) {
+        return Some(Stationary::Minimum);
+    }
+
+    let changes = (0..higher.len())
+        .filter(|&k| higher[k] != higher[(k + 1) % higher.len()])
+        .count();
+    if changes == 4 {
+        Some(Stationary::Saddle)
+    } else {
+        None
+    }
+}
+
+/// The grid of the landscape : the neighbours of every grid point and the row of the table
+/// that lies on it (None when the point is not in the table)
+pub fn landscape_graph(table: &LandscapeTable) -> Result<(NeighbourGraph, Vec<Option<usize>>)> {
+    match table.grid() {
+        Some(TorsionType::Peptide) => {
+            let phi = table.column("PHI").unwrap_or_default();
+            let psi = table.column("PSI").unwrap_or_default();
+            let (nodes, rows, columns) = grid_nodes(&phi, &psi, Some(360.));
+            Ok((NeighbourGraph::grid(rows, columns, true), nodes))
+        }
+        Some(TorsionType::Fivering) => {
+            let zx = table.column("Zx").unwrap_or_default();
+            let zy = table.column("Zy").unwrap_or_default();
+            let (nodes, rows, columns) = grid_nodes(&zx, &zy, None);
+            Ok((NeighbourGraph::grid(rows, columns, false), nodes))
+        }
+        Some(TorsionType::Sixring) => {
+            // the irregular sphere is built on the converged points only
+            let energies = usable_energies(table)?;
+            let theta = table.column("THETA").unwrap_or_default();
+            let phi = table.column("PHI").unwrap_or_default();
+            let rows: Vec<usize> = (0..table.len())
+                .filter(|&i| energies[i].is_finite())
+                .collect();
+            let points: Vec<[f64; 2]> = rows.iter().map(|&i| [theta[i], phi[i]]).collect();
+            Ok((
+                NeighbourGraph::sphere(&points),
+                rows.into_iter().map(Some).collect(),
+            ))
+        }
+        _ => bail!("The landscape table has no peptide, fivering or sixring grid"),
+    }
+}
+
+/// Place every row of the table on a grid, out of the distinct values along both axes
+fn grid_nodes(x: &[f64], y: &[f64], period: Option<f64>) -> (Vec<Option<usize>>, usize, usize) {
+    let x_axis = distinct_values(x, period);
+    let y_axis = distinct_values(y, period);
+    let position = |axis: &[f64], value: f64| {
+        let value = period.map_or(value, |p| value.rem_euclid(p));
+        axis.iter().position(|a| {
+            let d = (a - value).abs();
+            d < 1e-3 || period.is_some_and(|p| (d - p).abs() < 1e-3)
+        })
+    };
+
+    let mut nodes = vec![None; x_axis.len() * y_axis.len()];
+    for row in 0..x.len() {
+        if let (Some(i), Some(j)) = (position(&x_axis, x[row]), position(&y_axis, y[row])) {
+            nodes[i * y_axis.len() + j].get_or_insert(row);
+        }
+    }
+    (nodes, x_axis.len(), y_axis.len())
+}
+
+/// The sorted distinct values along an axis; on a periodic axis, the period is the same as 0
+fn distinct_values(values: &[f64], period: Option<f64>) -> Vec<f64> {
+    let mut distinct: Vec<f64> = Vec::new();
+    for &v in values.iter().filter(|v| v.is_finite()) {
+        let v = match period {
+            Some(p) if (v.rem_euclid(p) - p).abs() < 1e-3 => 0.,
+            Some(p) => v.rem_euclid(p),
+            None => v,
+        };
+        if !distinct.iter().any(|d| (d - v).abs() < 1e-3) {
+            distinct.push(v);
+        }
+    }
+    distinct.sort_by(|a, b| a.total_cmp(b));
+    distinct
+}
+
+/// All local minima and first-order saddle points of the landscape, by energy
+pub fn find_stationary(table: &LandscapeTable, flags: &Flags) -> Result<Vec<StationaryPoint>> {
+    let energies = relative_to_minimum(table, flags.unit)?;
+    let (graph, nodes) = landscape_graph(table)?;
+    let node_energies: Vec<f64> = nodes
+        .iter()
+        .map(|row| row.map_or(f64::NAN, |r| energies[r]))
+        .collect();
+
+    let mut points: Vec<StationaryPoint> = (0..graph.len())
+        .filter_map(|v| {
+            let kind = classify_point(&graph, &node_energies, v)?;
+            let row = nodes[v]?;
+            Some(StationaryPoint {
+                kind,
+                row,
+                energy: energies[row],
+                refined: None,
+            })
+        })
+        .collect();
+    points.sort_by(|a, b| {
+        (a.kind != Stationary::Minimum)
+            .cmp(&(b.kind != Stationary::Minimum))
+            .then(a.energy.total_cmp(&b.energy))
+    });
+
+    if flags.refine {
+        refine_points(table, flags, &graph, &nodes, &mut points)?;
+    }
+    Ok(points)
+}
+
+/// Refine the stationary points on the interpolated surface of the landscape
+fn refine_points(
+    table: &LandscapeTable,
+    flags: &Flags,
+    graph: &NeighbourGraph,
+    nodes: &[Option<usize>],
+    points: &mut [StationaryPoint],
+) -> Result<()> {
+    let [a, b] = match table.grid_columns()[..] {
+        [a, b, ..] => [a, b],
+        _ => bail!("The landscape table has no grid to refine on"),
+    };
+    let x = table.column(a).unwrap_or_default();
+    let y = table.column(b).unwrap_or_default();
+
+    match table.grid() {
+        Some(TorsionType::Peptide) => {
+            let surface = peptide_surface(table, flags)?;
+            for point in points.iter_mut() {
+                let (phi, psi) = (x[point.row], y[point.row]);
+                let f = |u: f64, v: f64| surface.evaluate(phi + u, psi + v);
+                point.refined = newton(f, surface.spacing, point.kind).map(|[u, v]| {
+                    let position = [(phi + u).rem_euclid(360.), (psi + v).rem_euclid(360.)];
+                    (position, f(u, v))
+                });
+            }
+        }
+        Some(TorsionType::Fivering) => {
+            let (surface, _) = fivering_surface(table, flags)?;
+            let zx_axis = distinct_values(&x, None);
+            let spacing = match zx_axis[..] {
+                [first, second, ..] => second - first,
+                _ => bail!("The fivering grid needs at least two points along Zx"),
+            };
+            for point in points.iter_mut() {
+                let (zx, zy) = (x[point.row], y[point.row]);
+                let f = |u: f64, v: f64| {
+                    let p = Pseudorotation::from_zx_zy(zx + u, zy + v);
+                    surface.evaluate(p.phase, p.amplitude)
+                };
+                point.refined =
+                    newton(f, spacing, point.kind).map(|[u, v]| ([zx + u, zy + v], f(u, v)));
+            }
+        }
+        Some(TorsionType::Sixring) => {
+            let (surface, _) = sixring_surface(table, flags)?;
+            for point in points.iter_mut() {
+                let (theta, phi) = (x[point.row], y[point.row]);
+                // the mean distance to the neighbours is the local grid spacing
+                let v = match nodes.iter().position(|&row| row == Some(point.row)) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let around = &graph.neighbours[v];
+                let spacing = around
+                    .iter()
+                    .filter_map(|&j| nodes[j])
+                    .map(|r| geodesic([theta, phi], [x[r], y[r]]))
+                    .sum::<f64>()
+                    / around.len() as f64;
+
+                let f = |u: f64, v: f64| {
+                    let [t, p] = tangent_point(theta, phi, u, v);
+                    surface.evaluate(t, p)
+                };
+                point.refined = newton(f, spacing, point.kind)
+                    .map(|[u, v]| (tangent_point(theta, phi, u, v), f(u, v)));
+            }
+        }
+        _ => bail!("The landscape table has no grid to refine on"),
+    }
+    Ok(())
+}
+
+/// The point (theta, phi) reached from (theta0, phi0) by a step (u, v) in the tangent plane,
+/// along increasing theta and phi
+pub fn tangent_point(theta0: f64, phi0: f64, u: f64, v: f64) -> [f64; 2] {
+    let centre = unit_vector(theta0, phi0);
+    let [e1, e2] = tangent_basis(theta0, phi0);
+    let p: Vec<f64> = (0..3).map(|k| centre[k] + u * e1[k] + v * e2[k]).collect();
+    let norm = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+
+    [
+        (p[2] / norm).clamp(-1., 1.).acos(),
+        p[1].atan2(p[0]).rem_euclid(2. * std::f64::consts::PI),
+    ]
+}
+
+/// Newton steps on f(u, v) from (0, 0) on, to the stationary point of the prompted kind.
+/// The derivatives are taken by central differences
+pub fn newton<F: Fn(f64, f64) -> f64>(f: F, spacing: f64, kind: Stationary) -> Option<[f64; 2]> {
+    let h = spacing * 1e-3;
+    let mut x = [0_f64; 2];
+
+    for _ in 0..MAX_ITERATIONS {
+        let [u, v] = x;
+        let f0 = f(u, v);
+        let g = [
+            (f(u + h, v) - f(u - h, v)) / (2. * h),
+            (f(u, v + h) - f(u, v - h)) / (2. * h),
+        ];
+        let huu = (f(u + h, v) - 2. * f0 + f(u - h, v)) / (h * h);
+        let hvv = (f(u, v + h) - 2. * f0 + f(u, v - h)) / (h * h);
+        let huv =
+            (f(u + h, v + h) - f(u + h, v - h) - f(u - h, v + h) + f(u - h, v - h)) / (4. * h * h);
+
+        let det = huu * hvv - huv * huv;
+        if !det.is_finite() || det == 0. {
+            return None;
+        }
+
+        let mut step = [
+            -(hvv * g[0] - huv * g[1]) / det,
+            -(huu * g[1] - huv * g[0]) / det,
+        ];
+        let length = step[0].hypot(step[1]);
+        if length > spacing / 2. {
+            step = step.map(|s| s * spacing / 2. / length);
+        }
+        x = [x[0] + step[0], x[1] + step[1]];
+
+        if x[0].hypot(x[1]) > 2. * spacing {
+            return None;
+        }
+        if length < spacing * 1e-6 {
+            let kept = match kind {
+                Stationary::Minimum => det > 0. && huu > 0.,
+                Stationary::Saddle => det < 0.,
+            };
+            return if kept { Some(x) } else { None };
+        }
+    }
+    None
+}
+
+/// Write the stationary points of the landscape, with their grid position and energy
+/// (and label), and report how many were found to stderr
+pub fn write_stationary<W: Write>(
+    table: &LandscapeTable,
+    flags: &Flags,
+    writer: &mut W,
+) -> Result<()> {
+    let points = find_stationary(table, flags)?;
+    let columns = table.grid_columns();
+    let values: Vec<Vec<f64>> = columns
+        .iter()
+        .map(|c| table.column(c).unwrap_or_default())
+        .collect();
+    let indices = table.text_column("INDEX");
+    let labelled = matches!(
+        table.grid(),
+        Some(TorsionType::Fivering) | Some(TorsionType::Sixring)
+    );
+
+    let mut header = format!(
+        "#{:>w1$} {:>w$}",
+        "INDEX",
+        "KIND",
+        w1 = WIDTH - 1,
+        w = WIDTH
+    );
+    for c in &columns {
+        header.push_str(&format!(" {:>w$}", c, w = WIDTH));
+    }
+    header.push_str(&format!(" {:>w$}", flags.unit.column(), w = WIDTH));
+    if labelled {
+        header.push_str(&format!(" {:>w$}", "LABEL", w = WIDTH));
+    }
+    if flags.refine {
+        for c in &columns {
+            header.push_str(&format!(" {:>w$}", format!("R_{}", c), w = WIDTH));
+        }
+        header.push_str(&format!(
+            " {:>w$}",
+            format!("R_{}", flags.unit.column()),
+            w = WIDTH
+        ));
+    }
+    writeln!(writer, "{}", header)?;
+
+    for point in &points {
+        let index = match &indices {
+            Some(i) => i[point.row].to_string(),
+            None => point.row.to_string(),
+        };
+        let mut line = format!("{:>w$} {:>w$}", index, point.kind.name(), w = WIDTH);
+        for v in &values {
+            line.push_str(&format!(
+                " {:w$.p$}",
+                v[point.row],
+                w = WIDTH,
+                p = PRECISION
+            ));
+        }
+        line.push_str(&format!(
+            " {:w$.p$}",
+            point.energy,
+            w = WIDTH,
+            p = PRECISION
+        ));
+        if labelled {
+            let (x, y) = (values[0][point.row], values[1][point.row]);
+            let label = match table.grid() {
+                Some(TorsionType::Sixring) => classify(x, y).conformer.label(&flags.ring_atoms),
+                _ => Pseudorotation::from_zx_zy(x, y).label(),
+            };
+            line.push_str(&format!(" {:>w$}", label, w = WIDTH));
+        }
+        if flags.refine {
+            let (position, energy) = point.refined.unwrap_or(([f64::NAN; 2], f64::NAN));
+            for v in position.iter().chain([energy].iter()) {
+                line.push_str(&format!(" {:w$.p$}", v, w = WIDTH, p = PRECISION));
+            }
+        }
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()?;
+
+    let count = |kind| points.iter().filter(|p| p.kind == kind).count();
+    let refined = points.iter().filter(|p| p.refined.is_some()).count();
+    eprintln!(
+        "Found {} local minima and {} first-order saddle points on {} points{}",
+        count(Stationary::Minimum),
+        count(Stationary::Saddle),
+        table.len(),
+        if flags.refine {
+            format!(" ; {} refined on the interpolated surface", refined)
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
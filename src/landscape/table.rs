@@ -0,0 +1,132 @@
+/// Read a landscape table (see `mod.rs`) back in, so it can be post-processed.
+///
+/// The lines are kept as they were written out, so every column (labels, status, ...) is
+/// preserved when new columns are added behind them. Values are only parsed when a column is
+/// asked for. Fields that are not numbers (labels, NaN) are read as NaN.
+///
+/// The kind of grid is recognised from the names of the coordinate columns :
+/// ```text
+///     X Y             -> peptide
+///     Zx Zy           -> fivering (with CHI -> nucleoside)
+///     RHO THETA PHI   -> sixring
+/// ```
+//
+// import modules
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+
+use crate::torsion_typing::TorsionType;
+
+/// A landscape table
+/// public `header` field : String. The header line, as it was read
+/// public `columns` field : Vec<String>. The names of the columns
+/// public `rows` field : Vec<String>. The lines of the table, as they were read
+pub struct LandscapeTable {
+    pub header: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<String>,
+}
+
+impl LandscapeTable {
+    /// The amount of points in the table
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The position of a column
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+
+    /// The fields of a column, as they were written out
+    pub fn text_column(&self, name: &str) -> Option<Vec<&str>> {
+        let k = self.position(name)?;
+        Some(
+            self.rows
+                .iter()
+                .map(|row| row.split_whitespace().nth(k).unwrap_or(""))
+                .collect(),
+        )
+    }
+
+    /// The values of a column
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        Some(
+            self.text_column(name)?
+                .iter()
+                .map(|field| field.parse::<f64>().unwrap_or(f64::NAN))
+                .collect(),
+        )
+    }
+
+    /// The kind of grid the table was collected on
+    pub fn grid(&self) -> Option<TorsionType> {
+        let has = |name: &str| self.position(name).is_some();
+
+        if has("RHO") && has("THETA") && has("PHI") {
+            Some(TorsionType::Sixring)
+        } else if has("Zx") && has("Zy") && has("CHI") {
+            Some(TorsionType::Nucleoside)
+        } else if has("Zx") && has("Zy") {
+            Some(TorsionType::Fivering)
+        } else if has("X") && has("Y") {
+            Some(TorsionType::Peptide)
+        } else {
+            None
+        }
+    }
+
+    /// The columns that place a point on the grid
+    pub fn grid_columns(&self) -> Vec<&'static str> {
+        match self.grid() {
+            Some(TorsionType::Peptide) => vec!["PHI", "PSI"],
+            Some(TorsionType::Fivering) => vec!["Zx", "Zy"],
+            Some(TorsionType::Nucleoside) => vec!["Zx", "Zy", "CHI"],
+            Some(TorsionType::Sixring) => vec!["THETA", "PHI"],
+            _ => vec![],
+        }
+    }
+}
+
+/// Read a landscape table from a file
+pub fn read_table(path: &str) -> Result<LandscapeTable> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Could not read the landscape table `{}`", path))?;
+    parse_table(&text)
+}
+
+/// Parse the content of a landscape table
+pub fn parse_table(text: &str) -> Result<LandscapeTable> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+    let header = match lines.next() {
+        Some(line) if line.starts_with('#') => line.to_string(),
+        _ => bail!("The landscape table does not start with a `#` header line"),
+    };
+    let columns: Vec<String> = header[1..].split_whitespace().map(String::from).collect();
+
+    let mut rows = Vec::new();
+    for (n, line) in lines.enumerate() {
+        let amount = line.split_whitespace().count();
+        if amount != columns.len() {
+            bail!(
+                "Line {} of the landscape table has {} fields instead of {}",
+                n + 2,
+                amount,
+                columns.len()
+            )
+        }
+        rows.push(line.to_string());
+    }
+
+    Ok(LandscapeTable {
+        header,
+        columns,
+        rows,
+    })
+}
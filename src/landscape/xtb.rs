@@ -0,0 +1,172 @@
+/// Read the results of a finished xtb job out of its directory.
+///
+/// xtb writes its log to stdout, so the output file is the first `.out` (or `.log`) file in the
+/// directory that carries the xtb banner. Looked for are :
+///     the last `TOTAL ENERGY` (Hartree),
+///     `normal termination of xtb`, and whether the geometry optimization converged.
+///
+/// xtb does not print the final values of the constrained dihedrals. These are measured on the
+/// optimised geometry (`xtbopt.xyz`), for the dihedrals in the `$constrain` block of the detailed
+/// input (the file passed with `xtb --input`) found in the same directory.
+/// ```text
+/// $constrain
+///    force constant=1.0
+///    dihedral: 1,2,3,4,-60.0
+/// $end
+/// ```
+//
+// import modules
+use std::fs;
+use std::path::Path;
+
+use crate::landscape::{find_output, JobResult, JobStatus, OutputParser};
+use crate::sixring::geometry::{dihedral, Coordinate};
+
+/// The banner every xtb output carries
+const XTB_BANNER: &str = "x T B";
+
+/// The optimised geometry xtb writes out
+const XTB_GEOMETRY: &str = "xtbopt.xyz";
+
+/// Reads the output and the optimised geometry of an xtb job
+pub struct Xtb;
+
+impl OutputParser for Xtb {
+    fn engine(&self) -> &'static str {
+        "xtb"
+    }
+
+    fn read_job(&self, directory: &Path) -> JobResult {
+        let output = find_output(directory, "out", XTB_BANNER)
+            .or_else(|| find_output(directory, "log", XTB_BANNER))
+            .and_then(|p| fs::read_to_string(p).ok());
+
+        let mut result = match output {
+            Some(text) => parse_xtb(&text),
+            None => return JobResult::missing(self.engine()),
+        };
+
+        // the geometry and the detailed input lie next to the output file
+        let directory = if directory.is_file() {
+            directory.parent().unwrap_or(Path::new(""))
+        } else {
+            directory
+        };
+        if let Ok(xyz) = fs::read_to_string(directory.join(XTB_GEOMETRY)) {
+            let constraints = detailed_input(directory)
+                .map(|text| parse_constraints(&text))
+                .unwrap_or_default();
+            result.dihedrals = measure_dihedrals(&parse_xyz(&xyz), &constraints);
+        }
+
+        result
+    }
+}
+
+/// Parse the content of an xtb output file
+pub fn parse_xtb(text: &str) -> JobResult {
+    let mut energy = None;
+    let mut normal = false;
+    let mut failed = false;
+
+    for line in text.lines() {
+        if line.contains("TOTAL ENERGY") {
+            energy = line
+                .split_whitespace()
+                .find_map(|field| field.parse::<f64>().ok());
+        } else if line.contains("normal termination of xtb") {
+            normal = true;
+        } else if line.contains("FAILED TO CONVERGE GEOMETRY OPTIMIZATION") {
+            failed = true;
+        }
+    }
+
+    let (status, reason) = if !normal {
+        (JobStatus::Crashed, Some("xtb did not terminate normally"))
+    } else if failed {
+        (
+            JobStatus::Unconverged,
+            Some("the optimization did not converge"),
+        )
+    } else {
+        (JobStatus::Converged, None)
+    };
+
+    JobResult {
+        status,
+        energy,
+        dihedrals: vec![],
+        reason: reason.map(String::from),
+    }
+}
+
+/// The first file in the directory with a `$constrain` block
+fn detailed_input(directory: &Path) -> Option<String> {
+    let mut paths: Vec<_> = fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find(|text| text.contains("$constrain"))
+}
+
+/// The atoms (starting at 1) of the constrained dihedrals, in the order of the `$constrain` block
+pub fn parse_constraints(text: &str) -> Vec<[usize; 4]> {
+    let mut constraints = Vec::new();
+    let mut in_block = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('$') {
+            in_block = line.starts_with("$constrain");
+            continue;
+        }
+
+        if let (true, Some(atoms)) = (in_block, line.strip_prefix("dihedral:")) {
+            let fields: Vec<usize> = atoms
+                .split(',')
+                .take(4)
+                .filter_map(|a| a.trim().parse::<usize>().ok())
+                .collect();
+            if let [a, b, c, d] = fields[..] {
+                constraints.push([a, b, c, d]);
+            }
+        }
+    }
+
+    constraints
+}
+
+/// The coordinates of all atoms in an xyz file
+pub fn parse_xyz(text: &str) -> Vec<Coordinate> {
+    text.lines()
+        .skip(2)
+        .filter_map(|line| {
+            let fields: Vec<f64> = line
+                .split_whitespace()
+                .skip(1)
+                .filter_map(|v| v.parse::<f64>().ok())
+                .collect();
+            match fields[..] {
+                [x, y, z, ..] => Some([x, y, z]),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Measure the dihedrals on the geometry, as long as all their atoms are present
+fn measure_dihedrals(atoms: &[Coordinate], constraints: &[[usize; 4]]) -> Vec<f64> {
+    constraints
+        .iter()
+        .map_while(|&[a, b, c, d]| {
+            let p = |i: usize| atoms.get(i.checked_sub(1)?).copied();
+            Some(dihedral(p(a)?, p(b)?, p(c)?, p(d)?))
+        })
+        .collect()
+}
@@ -1,6 +1,15 @@
 // Declare modules
 pub mod arguments;
 pub mod fivering;
+pub mod fourring;
+pub mod landscape;
+pub mod nucleoside;
+pub mod output;
+pub mod parallel;
 pub mod peptide;
+pub mod plot;
+pub mod product;
+pub mod ring;
+pub mod sevenring;
 pub mod sixring;
 pub mod torsion_typing;
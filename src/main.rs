@@ -1,8 +1,11 @@
 /// A program, written in the greatest language of all, to generate dihedral values
 /// in order to perform conformational sampling on :
 ///     peptide-like molecules (or any set of two torsion angles),
+///     four-membered rings (cyclobutanes, azetidines, oxetanes),
 ///     five-membered/furanose rings,
-///     six-membered/pyranose rings.
+///     six-membered/pyranose rings,
+///     seven-membered/septanose rings,
+///     nucleosides (furanose rings together with the glycosidic torsion angle).
 ///
 ///
 /// License   : MIT License
@@ -16,32 +19,73 @@ use std::env::args;
 
 use anyhow::Result;
 
-// Declare modules
-mod arguments;
-mod fivering;
-mod peptide;
-mod sixring;
-mod torsion_typing;
-
 // Use own libs
-use crate::arguments::Flags;
-use crate::torsion_typing::{Dihedrals, TorsionType};
+use puckers::arguments::Flags;
+use puckers::landscape::{print_landscape, process_landscape};
+use puckers::output::print_samples;
+use puckers::parallel::set_threads;
+use puckers::plot::{render, write_svg, PlotPoints};
+use puckers::torsion_typing::{SamplingSpace, TorsionType};
+use puckers::{fivering, fourring, nucleoside, peptide, product, ring, sevenring, sixring};
 
 fn main() -> Result<()> {
     // Disregard Clap, transcend humanity
     // collect CLI arguments and parse I/O
     let flags = Flags::return_cli_arguments(args().collect());
+    set_threads(flags.threads);
 
     // get the torsion angles and print out
     run(flags)
 }
 
 fn run(flags: Flags) -> Result<()> {
+    // A landscape table is post-processed, no sampling space is needed
+    if let Some(path) = &flags.landscape {
+        return process_landscape(path, &flags);
+    }
+
     // Match the type of torsion angles needed to generate and then output them
-    // method `.as_ref()` because we consume the Enum when matching
-    match flags.torsion_type.as_ref().unwrap() {
-        TorsionType::Peptide => peptide::peptide(&flags).print_values(flags),
-        TorsionType::Fivering => fivering::fivering(&flags).print_values(flags),
-        TorsionType::Sixring => sixring::sixring(&flags).print_values(flags),
+    // The streams compute and write out one conformation at a time
+    let space: Box<dyn SamplingSpace> = match flags.torsion_type.unwrap() {
+        TorsionType::Peptide => Box::new(peptide::peptide_stream(&flags)),
+        TorsionType::Fivering => match flags.path {
+            Some(ends) => Box::new(fivering::fivering_path(&flags, ends)),
+            None => Box::new(fivering::fivering_stream(&flags)),
+        },
+        TorsionType::Sixring => {
+            let stream = sixring::sixring_stream(&flags);
+            stream.dropped.report();
+            Box::new(stream)
+        }
+        TorsionType::Sevenring => {
+            let stream = sevenring::sevenring_stream(&flags);
+            stream.dropped.report();
+            Box::new(stream)
+        }
+        TorsionType::Fourring => {
+            let stream = fourring::fourring_stream(&flags);
+            stream.dropped.report();
+            Box::new(stream)
+        }
+        TorsionType::Nucleoside => Box::new(nucleoside::nucleoside_stream(&flags)),
+        TorsionType::Product => Box::new(product::product(&flags)),
+        TorsionType::Ring => {
+            let stream = ring::nring_stream(&flags);
+            stream.dropped.report();
+            Box::new(stream)
+        }
+    };
+
+    // Draw the grid, before it is written out
+    if let Some(image) = &flags.svg {
+        if let Some(points) = PlotPoints::from_space(space.as_ref(), flags.torsion_type.unwrap()) {
+            write_svg(image, &render(&points, flags.projection, ""))?;
+        }
+    }
+
+    // Either collect the results of the jobs that ran on the sampling space, or print it out
+    match &flags.collect {
+        Some(manifest) => print_landscape(space.as_ref(), manifest, &flags),
+        None => print_samples(space.as_ref(), &flags),
     }
 }
@@ -0,0 +1,95 @@
+use crate::arguments::Flags;
+use crate::fivering::pseudorotation::Pseudorotation;
+use crate::fivering::{fivering_stream, furanose_torsions, FuranoseStream};
+use crate::torsion_typing::{
+    names, ChiAxis, Label, LabelKind, Nucleoside, SamplingSpace, FURANOSE_COORDINATES,
+    NUCLEOSIDE_TORSIONS,
+};
+
+/// The amount of values on the chi axis; prompted or the same as the sugar axes
+pub fn chi_amount(flags: &Flags) -> usize {
+    flags.chi_num.unwrap_or(flags.num) as usize
+}
+
+/// Generate the torsion angles to use as restraints for nucleosides
+///
+/// The sugar pucker and the glycosidic torsion angle chi are sampled jointly, as the product of
+/// the fivering grid (Zx, Zy) and a periodic axis of chi in [0, 360).
+///
+/// The sugar grid runs over the outer index and chi over the inner index :
+///     i = sugar * CHI + chi
+pub fn nucleoside(flags: &Flags) -> Nucleoside {
+    let stream = nucleoside_stream(flags);
+
+    let mut n = Nucleoside::new(stream.len());
+
+    for sample in stream.samples(false) {
+        let i = sample.index;
+        n.nu1[i] = sample.torsions[0];
+        n.nu3[i] = sample.torsions[1];
+        n.chi[i] = sample.torsions[2];
+        n.zx[i] = sample.coordinates[0];
+        n.zy[i] = sample.coordinates[1];
+    }
+
+    n
+}
+
+/// The nucleoside sampling space, where every conformation is computed when asked for
+/// public `sugar` field : FuranoseStream
+/// public `chi_axis` field : ChiAxis
+pub struct NucleosideStream {
+    pub sugar: FuranoseStream,
+    pub chi_axis: ChiAxis,
+}
+
+/// Generate the stream of torsion angles for nucleosides
+pub fn nucleoside_stream(flags: &Flags) -> NucleosideStream {
+    NucleosideStream {
+        sugar: fivering_stream(flags),
+        chi_axis: ChiAxis::new(chi_amount(flags)),
+    }
+}
+
+impl NucleosideStream {
+    /// The index of the point on the sugar grid and on the chi axis
+    fn split(&self, i: usize) -> (usize, usize) {
+        let m_chi = self.chi_axis.chi.len();
+        (i / m_chi, i % m_chi)
+    }
+}
+
+impl SamplingSpace for NucleosideStream {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&NUCLEOSIDE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&FURANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.sugar.len() * self.chi_axis.chi.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        let (s, c) = self.split(i);
+        let (zx, zy) = self.sugar.zx_zy(s);
+        let [nu1, nu3] = furanose_torsions(zx, zy);
+        vec![nu1, nu3, self.chi_axis.chi[c]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        let (zx, zy) = self.sugar.zx_zy(self.split(i).0);
+        vec![zx, zy]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Pseudorotation)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        let (zx, zy) = self.sugar.zx_zy(self.split(i).0);
+        Some(Label::Pseudorotation(Pseudorotation::from_zx_zy(zx, zy)))
+    }
+}
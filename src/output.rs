@@ -0,0 +1,133 @@
+/// Write the points of a sampling space out as a plain text table
+///
+/// This is the only place where the values are formatted. The generators hand out `Sample`
+/// records (see `torsion_typing.rs`), so library users can consume them without any output.
+///
+/// A `#` pound symbol is added on the first line, to act as a comment symbol
+/// for when one wants to easily parse it through numpy, shell scripts or as an easy identifier.
+/// Every value takes up ten characters with three decimals.
+/// Only a chunk of lines is held in memory at once, whatever the size of the sampling space.
+///
+//
+// import modules
+use std::io::{BufWriter, ErrorKind, Write};
+
+use anyhow::Result;
+
+use crate::arguments::Flags;
+use crate::parallel::map_indices;
+use crate::torsion_typing::{Label, LabelKind, Sample, SamplingSpace};
+
+const WIDTH: usize = 10;
+const PRECISION: usize = 3;
+
+/// The amount of lines that are computed before they are written out
+const CHUNK: usize = 4096;
+
+/// The header line of the table
+pub fn header(space: &dyn SamplingSpace, flags: &Flags) -> String {
+    let mut columns = space.torsion_names();
+    columns.extend(space.coordinate_names());
+
+    // the pound symbol takes up the first character of the first column
+    let mut line = String::from("#");
+    for (k, name) in columns.iter().enumerate() {
+        if k == 0 {
+            line.push_str(&format!("{:>width$}", name, width = WIDTH - 1));
+        } else {
+            line.push_str(&format!(" {:>width$}", name, width = WIDTH));
+        }
+    }
+
+    // optional columns
+    if flags.labels {
+        match space.label_kind() {
+            Some(LabelKind::Conformer) => {
+                line.push_str(&format!(" {:>w$} {:>w$}", "LABEL", "DIST", w = WIDTH))
+            }
+            Some(LabelKind::Pseudorotation) => line.push_str(&format!(
+                " {:>w$} {:>w$} {:>18} {:>6}",
+                "P",
+                "FORM",
+                "PUCKER",
+                "SECTOR",
+                w = WIDTH
+            )),
+            None => (),
+        }
+    }
+    line
+}
+
+/// A single line of the table
+pub fn format_sample(sample: &Sample, flags: &Flags) -> String {
+    let values: Vec<String> = sample
+        .torsions
+        .iter()
+        .chain(sample.coordinates.iter())
+        .map(|v| format!("{:w$.p$}", v, w = WIDTH, p = PRECISION))
+        .collect();
+
+    let mut line = values.join(" ");
+
+    match &sample.label {
+        // the nearest canonical conformer and its distance (degrees)
+        Some(Label::Conformer(nearest)) => line.push_str(&format!(
+            " {:>w$} {:w$.p$}",
+            nearest.conformer.label(&flags.ring_atoms),
+            nearest.distance,
+            w = WIDTH,
+            p = PRECISION
+        )),
+        // the pseudorotation phase, the nearest form and its sector
+        Some(Label::Pseudorotation(wheel)) => line.push_str(&format!(
+            " {:w$.p$} {:>w$} {:>18} {:>6}",
+            wheel.phase,
+            wheel.label(),
+            wheel.description(),
+            wheel.sector_letter(),
+            w = WIDTH,
+            p = PRECISION
+        )),
+        None => (),
+    }
+    line
+}
+
+/// Write the table of the sampling space to any writer
+pub fn write_samples<W: Write>(
+    space: &dyn SamplingSpace,
+    flags: &Flags,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writeln!(writer, "{}", header(space, flags))?;
+
+    // the lines are computed per chunk, which can be split across threads (see `parallel.rs`)
+    let mut start = 0;
+    while start < space.len() {
+        let amount = CHUNK.min(space.len() - start);
+        let lines = map_indices(amount, |k| {
+            format_sample(&space.sample(start + k, flags.labels), flags)
+        });
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+        start += amount;
+    }
+    writer.flush()
+}
+
+/// Print the table of the sampling space to stdout
+/// A closed pipe (e.g. `puckers --sixring 630 | head`) is not an error
+pub fn print_samples(space: &dyn SamplingSpace, flags: &Flags) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    match write_samples(space, flags, &mut writer) {
+        Ok(_) => Ok(()),
+        Err(e) => match e.kind() {
+            ErrorKind::BrokenPipe => Ok(()),
+            _ => Err(e.into()),
+        },
+    }
+}
@@ -0,0 +1,62 @@
+/// Split the generation of conformations across threads
+///
+/// This is only done when puckers is built with the `parallel` feature :
+///
+/// ```text
+///     $ cargo build --release --features parallel
+///     $ puckers --sixring 1000000 --threads 8
+/// ```
+///
+/// Without the feature, the same functions run on a single thread. The results are always
+/// returned in the order of their indices, so the output does not depend on the amount of
+/// threads.
+///
+//
+// import modules
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Set the amount of threads to use. Without a prompted amount, all available cores are used
+#[cfg(feature = "parallel")]
+pub fn set_threads(threads: Option<usize>) {
+    if let Some(num) = threads {
+        // the global pool can only be built once; later calls keep the first amount of threads
+        if let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(num)
+            .build_global()
+        {
+            eprintln!(
+                "`--threads` not applied ({}). Using the threads of the existing pool.",
+                e
+            )
+        }
+    }
+}
+
+/// Set the amount of threads to use. Without the `parallel` feature, this is always one
+#[cfg(not(feature = "parallel"))]
+pub fn set_threads(threads: Option<usize>) {
+    if threads.is_some_and(|num| num > 1) {
+        eprintln!("`--threads` requires puckers to be built with the `parallel` feature. Using a single thread.")
+    }
+}
+
+/// Apply `f` to every index in 0..n and collect the results in the order of the indices
+#[cfg(feature = "parallel")]
+pub fn map_indices<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    (0..n).into_par_iter().map(f).collect()
+}
+
+/// Apply `f` to every index in 0..n and collect the results in the order of the indices
+#[cfg(not(feature = "parallel"))]
+pub fn map_indices<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    (0..n).map(f).collect()
+}
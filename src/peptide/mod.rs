@@ -1,25 +1,58 @@
 use crate::arguments::Flags;
-use crate::torsion_typing::{Peptide, PeptideAxes};
+use crate::torsion_typing::{
+    names, Peptide, PeptideAxes, SamplingSpace, PEPTIDE_COORDINATES, PEPTIDE_TORSIONS,
+};
 
 /// Generate the torsion angles to use as restraints for peptide-like molecules
 pub fn peptide(flags: &Flags) -> Peptide {
-    let amount: u64 = flags.num * flags.num;
+    let stream = peptide_stream(flags);
 
-    let bb = PeptideAxes::new(flags.num as usize);
-
-    let mut p = Peptide::new(amount as usize);
-
-    let mut x: f64;
-    let mut y: f64;
-    for i in 0..amount as usize {
-        // For every x value, return all y values
-        x = (i as f64 / flags.num as f64).floor(); // floor, to return x axis value
-        y = i as f64 % flags.num as f64; // return with modulo, to return y axis value
+    let mut p = Peptide::new(stream.len());
 
+    for sample in stream.samples(false) {
         // fill out the array
-        p.phi[i as usize] = bb.x[x as usize];
-        p.psi[i as usize] = bb.y[y as usize];
+        p.phi[sample.index] = sample.torsions[0];
+        p.psi[sample.index] = sample.torsions[1];
     }
 
     p
 }
+
+/// The peptide-like sampling space, where every conformation is computed when asked for
+/// public `axes` field : PeptideAxes
+/// public `num` field : usize. The amount of values on either axis
+pub struct PeptideStream {
+    pub axes: PeptideAxes,
+    pub num: usize,
+}
+
+/// Generate the stream of torsion angles for peptide-like molecules
+pub fn peptide_stream(flags: &Flags) -> PeptideStream {
+    PeptideStream {
+        axes: PeptideAxes::new(flags.num as usize),
+        num: flags.num as usize,
+    }
+}
+
+impl SamplingSpace for PeptideStream {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&PEPTIDE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&PEPTIDE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.num * self.num
+    }
+
+    /// For every x value, return all y values
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.axes.x[i / self.num], self.axes.y[i % self.num]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        self.torsions(i)
+    }
+}
@@ -0,0 +1,553 @@
+/// Draw a sampling space or a landscape as an SVG image, to check a grid without any plotting
+/// library.
+///
+/// Every point is drawn where it lies, coloured by its relative energy when there is one
+/// (a `--landscape`), or as a plain dot when there is not (a sampling space). Points without a
+/// converged energy are drawn hollow. The colours run from dark blue (low) to yellow (high).
+///
+/// ```text
+///     peptide  : the (phi, psi) grid, Ramachandran style. A landscape is drawn as a heatmap.
+///     fivering : the (Zx, Zy) plane with the pseudorotation wheel on top of it : circles of equal
+///                amplitude, and the envelope and twist forms every 18 degrees of P.
+///     sixring  : a projection of the sphere, with the 38 IUPAC conformers as landmarks
+///         equirectangular : phi against theta, 4C1 on top,
+///         hammer          : the equal-area Hammer projection, centred on phi = 180 degrees,
+///         polar           : two equal-area (Lambert) discs around the chairs, as Stoddart diagrams.
+/// ```
+//
+// import modules
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+
+use crate::fivering::pseudorotation::FURANOSE_FORMS;
+use crate::landscape::energies::{relative_to_minimum, EnergyUnit};
+use crate::landscape::table::LandscapeTable;
+use crate::sixring::conformers::CANONICAL_CONFORMERS;
+use crate::torsion_typing::{SamplingSpace, TorsionType};
+
+/// The size (pixels) of the square plotting area, and the margin around it
+const SIZE: f64 = 480.;
+const MARGIN: f64 = 60.;
+
+/// The width (pixels) of the colour bar, and the room it takes up on the right
+const BAR_WIDTH: f64 = 16.;
+const BAR_ROOM: f64 = 90.;
+
+/// The colours of the colour scale, evenly spread from low to high (viridis)
+const COLOURS: [[f64; 3]; 5] = [
+    [68., 1., 84.],
+    [59., 82., 139.],
+    [33., 145., 140.],
+    [94., 201., 98.],
+    [253., 231., 37.],
+];
+
+/// The ways to project the sixring sphere onto the image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    Equirectangular,
+    Hammer,
+    Polar,
+}
+
+impl Projection {
+    /// Parse the name of a projection, as prompted on the command line
+    pub fn from_name(name: &str) -> Option<Projection> {
+        match name.to_lowercase().as_str() {
+            "equirectangular" => Some(Projection::Equirectangular),
+            "hammer" => Some(Projection::Hammer),
+            "polar" | "stoddart" => Some(Projection::Polar),
+            _ => None,
+        }
+    }
+}
+
+/// The points to draw
+/// public `grid` field : TorsionType. Peptide, Fivering or Sixring
+/// public `positions` field : Vec<[f64; 2]>. (phi, psi) or (Zx, Zy) in degrees, (theta, phi) in radians
+/// public `values` field : Option<Vec<f64>>. The value of every point (NaN when it has none)
+/// public `title` field : String
+pub struct PlotPoints {
+    pub grid: TorsionType,
+    pub positions: Vec<[f64; 2]>,
+    pub values: Option<Vec<f64>>,
+    pub title: String,
+}
+
+impl PlotPoints {
+    /// The points of a sampling space, without values
+    pub fn from_space(space: &dyn SamplingSpace, grid: TorsionType) -> Option<PlotPoints> {
+        let position = |i: usize| -> [f64; 2] {
+            match grid {
+                TorsionType::Peptide => {
+                    let t = space.torsions(i);
+                    [t[0], t[1]]
+                }
+                TorsionType::Sixring => {
+                    let c = space.coordinates(i);
+                    [c[1], c[2]]
+                }
+                _ => {
+                    let c = space.coordinates(i);
+                    [c[0], c[1]]
+                }
+            }
+        };
+
+        match grid {
+            TorsionType::Peptide | TorsionType::Fivering | TorsionType::Sixring => {
+                Some(PlotPoints {
+                    grid,
+                    positions: (0..space.len()).map(position).collect(),
+                    values: None,
+                    title: format!("{} : {} points", grid_name(grid), space.len()),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The grid points of a landscape, with their energies relative to the global minimum
+    pub fn from_landscape(table: &LandscapeTable, unit: EnergyUnit) -> Result<PlotPoints> {
+        let grid = match table.grid() {
+            Some(grid @ (TorsionType::Peptide | TorsionType::Fivering | TorsionType::Sixring)) => {
+                grid
+            }
+            _ => bail!("Only a peptide, fivering or sixring landscape can be drawn"),
+        };
+        let columns: Vec<Vec<f64>> = table
+            .grid_columns()
+            .iter()
+            .map(|name| table.column(name).unwrap_or_default())
+            .collect();
+        let positions: Vec<[f64; 2]> = (0..table.len())
+            .map(|r| [columns[0][r], columns[1][r]])
+            .collect();
+
+        Ok(PlotPoints {
+            grid,
+            title: format!("{} landscape : {} points", grid_name(grid), positions.len()),
+            positions,
+            values: Some(relative_to_minimum(table, unit)?),
+        })
+    }
+}
+
+fn grid_name(grid: TorsionType) -> String {
+    format!("{:?}", grid).to_lowercase()
+}
+
+/// The elements of the image, written out as text
+struct Svg {
+    text: String,
+}
+
+impl Svg {
+    fn new(width: f64, height: f64) -> Svg {
+        let mut text = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\" font-family=\"sans-serif\" font-size=\"11\">\n",
+            width, height, width, height
+        );
+        text.push_str(&format!(
+            "<rect width=\"{:.0}\" height=\"{:.0}\" fill=\"white\"/>\n",
+            width, height
+        ));
+        Svg { text }
+    }
+
+    fn line(&mut self, a: [f64; 2], b: [f64; 2], stroke: &str) {
+        self.text.push_str(&format!(
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\" stroke-width=\"0.6\"/>\n",
+            a[0], a[1], b[0], b[1], stroke
+        ));
+    }
+
+    fn polyline(&mut self, points: &[[f64; 2]], stroke: &str) {
+        let coordinates: Vec<String> = points
+            .iter()
+            .map(|p| format!("{:.2},{:.2}", p[0], p[1]))
+            .collect();
+        self.text.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.6\"/>\n",
+            coordinates.join(" "),
+            stroke
+        ));
+    }
+
+    fn rect(&mut self, corner: [f64; 2], size: [f64; 2], fill: &str, stroke: &str) {
+        self.text.push_str(&format!(
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\" stroke=\"{}\"/>\n",
+            corner[0], corner[1], size[0], size[1], fill, stroke
+        ));
+    }
+
+    fn circle(&mut self, centre: [f64; 2], radius: f64, fill: &str, stroke: &str) {
+        self.text.push_str(&format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"0.6\"/>\n",
+            centre[0], centre[1], radius, fill, stroke
+        ));
+    }
+
+    fn label(&mut self, at: [f64; 2], text: &str, anchor: &str) {
+        self.text.push_str(&format!(
+            "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"{}\">{}</text>\n",
+            at[0],
+            at[1],
+            anchor,
+            escape(text)
+        ));
+    }
+
+    fn finish(mut self) -> String {
+        self.text.push_str("</svg>\n");
+        self.text
+    }
+}
+
+/// Escape the characters that have a meaning in SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The colour of a fraction in [0, 1] of the colour scale
+pub fn colour(fraction: f64) -> String {
+    let f = fraction.clamp(0., 1.) * (COLOURS.len() - 1) as f64;
+    let k = (f.floor() as usize).min(COLOURS.len() - 2);
+    let t = f - k as f64;
+    let c: Vec<u8> = (0..3)
+        .map(|i| (COLOURS[k][i] + t * (COLOURS[k + 1][i] - COLOURS[k][i])).round() as u8)
+        .collect();
+    format!("#{:02x}{:02x}{:02x}", c[0], c[1], c[2])
+}
+
+/// The range of the finite values, if any
+fn value_range(values: &[f64]) -> Option<(f64, f64)> {
+    let finite = values.iter().filter(|v| v.is_finite());
+    let low = finite.clone().fold(f64::INFINITY, |m, v| m.min(*v));
+    let high = finite.fold(f64::NEG_INFINITY, |m, v| m.max(*v));
+    if low.is_finite() {
+        Some((low, high))
+    } else {
+        None
+    }
+}
+
+/// The fill of a point : its colour, white (hollow) without a converged value,
+/// or black when nothing is coloured
+fn fill(points: &PlotPoints, range: Option<(f64, f64)>, i: usize) -> String {
+    match (&points.values, range) {
+        (Some(values), Some((low, high))) if values[i].is_finite() => {
+            let span = if high > low { high - low } else { 1. };
+            colour((values[i] - low) / span)
+        }
+        (Some(_), _) => "white".to_string(),
+        (None, _) => "black".to_string(),
+    }
+}
+
+/// The smallest distance between the distinct values along an axis
+fn spacing(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut distinct: Vec<f64> = values.collect();
+    distinct.sort_by(|a, b| a.total_cmp(b));
+    distinct
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|d| *d > 1e-6)
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Draw the colour bar on the right of the plotting area, which starts at `top`
+fn colour_bar(svg: &mut Svg, range: Option<(f64, f64)>, left: f64, top: f64, unit: &str) {
+    let (low, high) = match range {
+        Some(range) => range,
+        None => return,
+    };
+    let steps = 50;
+    let height = SIZE / steps as f64;
+    for k in 0..steps {
+        let fraction = 1. - (k as f64 + 0.5) / steps as f64;
+        let c = colour(fraction);
+        svg.rect(
+            [left, top + k as f64 * height],
+            [BAR_WIDTH, height + 0.5],
+            &c,
+            "none",
+        );
+    }
+    svg.rect([left, top], [BAR_WIDTH, SIZE], "none", "black");
+    svg.label(
+        [left + BAR_WIDTH + 4., top + 8.],
+        &format!("{:.2}", high),
+        "start",
+    );
+    svg.label(
+        [left + BAR_WIDTH + 4., top + SIZE],
+        &format!("{:.2}", low),
+        "start",
+    );
+    svg.label([left, top - 8.], unit, "start");
+}
+
+/// Draw the points as an SVG image; `unit` names the values, if any
+pub fn render(points: &PlotPoints, projection: Projection, unit: &str) -> String {
+    match points.grid {
+        TorsionType::Peptide => render_peptide(points, unit),
+        TorsionType::Fivering => render_fivering(points, unit),
+        _ => render_sphere(points, projection, unit),
+    }
+}
+
+/// The Ramachandran plot of (phi, psi), both in [0, 360]
+fn render_peptide(points: &PlotPoints, unit: &str) -> String {
+    let range = points.values.as_deref().and_then(value_range);
+    let mut svg = Svg::new(MARGIN * 2. + SIZE + BAR_ROOM, MARGIN * 2. + SIZE);
+    let scale = SIZE / 360.;
+    let at = |p: [f64; 2]| [MARGIN + p[0] * scale, MARGIN + SIZE - p[1] * scale];
+
+    // a landscape is drawn as cells around its grid points, clipped to the plotting area
+    let cell = spacing(points.positions.iter().map(|p| p[0]));
+    svg.text.push_str(&format!(
+        "<clipPath id=\"area\"><rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"/></clipPath>\n<g clip-path=\"url(#area)\">\n",
+        MARGIN, MARGIN, SIZE, SIZE
+    ));
+    for (i, p) in points.positions.iter().enumerate() {
+        let f = fill(points, range, i);
+        match (&points.values, cell) {
+            (Some(_), Some(h)) => {
+                let [x, y] = at([p[0] - h / 2., p[1] + h / 2.]);
+                svg.rect([x, y], [h * scale, h * scale], &f, "none");
+            }
+            _ => svg.circle(at(*p), 1.5, &f, "none"),
+        }
+    }
+    svg.text.push_str("</g>\n");
+
+    frame(&mut svg, "PHI", "PSI");
+    for k in 0..=6 {
+        let v = k as f64 * 60.;
+        let [x, _] = at([v, 0.]);
+        let [_, y] = at([0., v]);
+        svg.line([x, MARGIN + SIZE], [x, MARGIN + SIZE + 5.], "black");
+        svg.label([x, MARGIN + SIZE + 18.], &format!("{:.0}", v), "middle");
+        svg.line([MARGIN - 5., y], [MARGIN, y], "black");
+        svg.label([MARGIN - 8., y + 4.], &format!("{:.0}", v), "end");
+    }
+    colour_bar(&mut svg, range, MARGIN * 2. + SIZE - 30., MARGIN, unit);
+    svg.label([MARGIN + SIZE / 2., MARGIN / 2.], &points.title, "middle");
+    svg.finish()
+}
+
+/// The frame of the square plotting area, with the names of both axes
+fn frame(svg: &mut Svg, x_name: &str, y_name: &str) {
+    svg.rect([MARGIN, MARGIN], [SIZE, SIZE], "none", "black");
+    svg.label([MARGIN + SIZE / 2., MARGIN + SIZE + 40.], x_name, "middle");
+    svg.text.push_str(&format!(
+        "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\" transform=\"rotate(-90 {:.2} {:.2})\">{}</text>\n",
+        MARGIN - 40.,
+        MARGIN + SIZE / 2.,
+        MARGIN - 40.,
+        MARGIN + SIZE / 2.,
+        y_name
+    ));
+}
+
+/// The (Zx, Zy) plane with the pseudorotation wheel
+fn render_fivering(points: &PlotPoints, unit: &str) -> String {
+    let range = points.values.as_deref().and_then(value_range);
+    let mut svg = Svg::new(MARGIN * 2. + SIZE + BAR_ROOM, MARGIN * 2. + SIZE);
+
+    // the plane spans the largest coordinate, and at least the usual 60 degrees
+    let extent = points
+        .positions
+        .iter()
+        .fold(60_f64, |m, p| m.max(p[0].abs()).max(p[1].abs()));
+    let extent = (extent / 10.).ceil() * 10.;
+    let scale = SIZE / (2. * extent) * 0.85;
+    let centre = [MARGIN + SIZE / 2., MARGIN + SIZE / 2.];
+    let at = |p: [f64; 2]| [centre[0] + p[0] * scale, centre[1] - p[1] * scale];
+
+    // circles of equal amplitude, and a spoke every 36 degrees of P
+    let mut amplitude = 20.;
+    while amplitude <= extent {
+        svg.circle(centre, amplitude * scale, "none", "#bbbbbb");
+        svg.label(
+            at([amplitude * 0.05, amplitude + 1.]),
+            &format!("{:.0}", amplitude),
+            "start",
+        );
+        amplitude += 20.;
+    }
+    let rim = extent * 1.04;
+    for (k, form) in FURANOSE_FORMS.iter().enumerate() {
+        let p = (k as f64 * 18.).to_radians();
+        let direction = [p.cos(), p.sin()];
+        if k % 2 == 0 {
+            svg.line(
+                centre,
+                at([extent * direction[0], extent * direction[1]]),
+                "#dddddd",
+            );
+        }
+        let [x, y] = at([rim * direction[0], rim * direction[1]]);
+        svg.label([x, y + 4.], &form.label(), "middle");
+    }
+
+    let cell = spacing(points.positions.iter().map(|p| p[0]));
+    for (i, p) in points.positions.iter().enumerate() {
+        let f = fill(points, range, i);
+        match (&points.values, cell) {
+            (Some(_), Some(h)) => {
+                let [x, y] = at([p[0] - h / 2., p[1] + h / 2.]);
+                svg.rect([x, y], [h * scale, h * scale], &f, "none");
+            }
+            _ => svg.circle(at(*p), 2., &f, "none"),
+        }
+    }
+
+    frame(&mut svg, "Zx", "Zy");
+    colour_bar(&mut svg, range, MARGIN * 2. + SIZE - 30., MARGIN, unit);
+    svg.label([MARGIN + SIZE / 2., MARGIN / 2.], &points.title, "middle");
+    svg.finish()
+}
+
+/// The sphere, in one of the projections. Positions are (theta, phi) in radians
+fn render_sphere(points: &PlotPoints, projection: Projection, unit: &str) -> String {
+    let range = points.values.as_deref().and_then(value_range);
+    let (width, height) = match projection {
+        Projection::Polar => (2. * SIZE + 3. * MARGIN, SIZE + 2. * MARGIN),
+        _ => (2. * SIZE + 2. * MARGIN, SIZE + 2. * MARGIN),
+    };
+    let mut svg = Svg::new(width + BAR_ROOM, height);
+
+    // every point lands on one or two (polar : equator on both discs) places
+    let project = |theta: f64, phi: f64| -> Vec<[f64; 2]> {
+        match projection {
+            Projection::Equirectangular => vec![[
+                MARGIN + phi.rem_euclid(2. * PI) / (2. * PI) * 2. * SIZE,
+                MARGIN + theta / PI * SIZE,
+            ]],
+            Projection::Hammer => {
+                let latitude = FRAC_PI_2 - theta;
+                let longitude = phi.rem_euclid(2. * PI) - PI;
+                let d = (1. + latitude.cos() * (longitude / 2.).cos()).sqrt();
+                let x = 2. * SQRT_2 * latitude.cos() * (longitude / 2.).sin() / d;
+                let y = SQRT_2 * latitude.sin() / d;
+                vec![[
+                    MARGIN + SIZE + x / (2. * SQRT_2) * SIZE,
+                    MARGIN + SIZE / 2. - y / SQRT_2 * SIZE / 2.,
+                ]]
+            }
+            Projection::Polar => {
+                let disc = |centre: f64, c: f64| {
+                    // Lambert : r = 2 sin(c / 2), which is sqrt(2) on the equator
+                    let r = 2. * (c / 2.).sin() / SQRT_2 * SIZE / 2.;
+                    [centre + r * phi.cos(), MARGIN + SIZE / 2. - r * phi.sin()]
+                };
+                let mut at = Vec::new();
+                if theta <= FRAC_PI_2 + 1e-9 {
+                    at.push(disc(MARGIN + SIZE / 2., theta));
+                }
+                if theta >= FRAC_PI_2 - 1e-9 {
+                    at.push(disc(2. * MARGIN + 1.5 * SIZE, PI - theta));
+                }
+                at
+            }
+        }
+    };
+
+    // the outline, parallels and meridians
+    let parallels = [30_f64, 60., 90., 120., 150.];
+    match projection {
+        Projection::Equirectangular => {
+            svg.rect([MARGIN, MARGIN], [2. * SIZE, SIZE], "none", "black");
+            for theta in [0_f64, 30., 60., 90., 120., 150., 180.] {
+                let y = MARGIN + theta / 180. * SIZE;
+                svg.line([MARGIN - 5., y], [MARGIN, y], "black");
+                svg.label([MARGIN - 8., y + 4.], &format!("{:.0}", theta), "end");
+            }
+            for phi in (0..=6).map(|k| k as f64 * 60.) {
+                let x = MARGIN + phi / 360. * 2. * SIZE;
+                svg.line([x, MARGIN + SIZE], [x, MARGIN + SIZE + 5.], "black");
+                svg.label([x, MARGIN + SIZE + 18.], &format!("{:.0}", phi), "middle");
+            }
+            svg.label([MARGIN + SIZE, MARGIN + SIZE + 40.], "PHI", "middle");
+            svg.label([MARGIN - 40., MARGIN + SIZE / 2.], "THETA", "middle");
+        }
+        Projection::Hammer => {
+            for theta in parallels {
+                let line: Vec<[f64; 2]> = (0..=72)
+                    .map(|k| project(theta.to_radians(), (k as f64 * 5.).to_radians())[0])
+                    .collect();
+                svg.polyline(&line, "#cccccc");
+            }
+            for phi in (0..6).map(|k| k as f64 * 60.) {
+                let line: Vec<[f64; 2]> = (0..=36)
+                    .map(|k| project((k as f64 * 5.).to_radians(), phi.to_radians())[0])
+                    .collect();
+                svg.polyline(&line, "#cccccc");
+            }
+            let outline: Vec<[f64; 2]> = (0..=72)
+                .map(|k| project((k as f64 * 2.5).to_radians(), 2. * PI - 1e-9)[0])
+                .chain((0..=72).map(|k| project(PI - (k as f64 * 2.5).to_radians(), 0.)[0]))
+                .collect();
+            svg.polyline(&outline, "black");
+        }
+        Projection::Polar => {
+            for (centre, title) in [
+                (MARGIN + SIZE / 2., "THETA 0 - 90"),
+                (2. * MARGIN + 1.5 * SIZE, "THETA 90 - 180"),
+            ] {
+                for theta in [30_f64, 60., 90.] {
+                    let r = 2. * (theta.to_radians() / 2.).sin() / SQRT_2 * SIZE / 2.;
+                    let stroke = if theta == 90. { "black" } else { "#cccccc" };
+                    svg.circle([centre, MARGIN + SIZE / 2.], r, "none", stroke);
+                }
+                for phi in (0..6).map(|k| (k as f64 * 60.).to_radians()) {
+                    svg.line(
+                        [centre, MARGIN + SIZE / 2.],
+                        [
+                            centre + SIZE / 2. * phi.cos(),
+                            MARGIN + SIZE / 2. - SIZE / 2. * phi.sin(),
+                        ],
+                        "#cccccc",
+                    );
+                }
+                svg.label([centre, MARGIN - 8.], title, "middle");
+            }
+        }
+    }
+
+    let radius = if points.values.is_some() { 3. } else { 1.5 };
+    for (i, p) in points.positions.iter().enumerate() {
+        let f = fill(points, range, i);
+        let stroke = if points.values.is_some() {
+            "#555555"
+        } else {
+            "none"
+        };
+        for at in project(p[0], p[1]) {
+            svg.circle(at, radius, &f, stroke);
+        }
+    }
+
+    // the IUPAC conformers as landmarks
+    for conformer in CANONICAL_CONFORMERS.iter() {
+        for [x, y] in project(conformer.theta.to_radians(), conformer.phi.to_radians()) {
+            svg.circle([x, y], 1.5, "red", "none");
+            svg.label([x, y - 4.], &conformer.default_label(), "middle");
+        }
+    }
+
+    colour_bar(&mut svg, range, width, MARGIN, unit);
+    svg.label([width / 2., MARGIN / 3.], &points.title, "middle");
+    svg.finish()
+}
+
+/// Write the image to a file
+pub fn write_svg(path: &str, svg: &str) -> Result<()> {
+    fs::write(path, svg).with_context(|| format!("Could not write the image `{}`", path))
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::arguments::Flags;
+use crate::torsion_typing::{SamplingSpace, TorsionType};
+use crate::{fivering, peptide, sixring};
+
+/// The Cartesian product of several sampling spaces
+///
+/// Every point of the product is enumerated by a single index. The factors are nested like the
+/// axes of the peptide grid : the last factor runs fastest.
+///
+/// ```text
+///     i = ((i_1 * n_2) + i_2) * n_3 + i_3 ...
+/// ```
+///
+/// The columns of all factors are merged : first the torsion angles of every factor, then their
+/// coordinates. A name that occurs in more than one factor is suffixed with the (1-based)
+/// number of its factor, e.g. `PHI_1` for a peptide and `PHI_2` for a pyranose.
+/// public `factors` field : Vec<Box<dyn SamplingSpace>>
+pub struct ProductSpace {
+    pub factors: Vec<Box<dyn SamplingSpace>>,
+}
+
+impl ProductSpace {
+    /// Combine the given sampling spaces
+    pub fn new(factors: Vec<Box<dyn SamplingSpace>>) -> ProductSpace {
+        ProductSpace { factors }
+    }
+
+    /// The index of the point in every factor, that belongs to the i-th point of the product
+    pub fn indices(&self, i: usize) -> Vec<usize> {
+        let mut rest = i;
+        let mut idx = vec![0; self.factors.len()];
+        for (k, space) in self.factors.iter().enumerate().rev() {
+            idx[k] = rest % space.len();
+            rest /= space.len();
+        }
+        idx
+    }
+
+    /// Suffix the names that occur in more than one factor with the number of their factor
+    fn merged_names(&self, names: impl Fn(&dyn SamplingSpace) -> Vec<String>) -> Vec<String> {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for space in self.factors.iter() {
+            let mut all = space.torsion_names();
+            all.extend(space.coordinate_names());
+            all.sort();
+            all.dedup();
+            for name in all {
+                *occurrences.entry(name).or_insert(0) += 1;
+            }
+        }
+
+        let mut merged = Vec::new();
+        for (k, space) in self.factors.iter().enumerate() {
+            for name in names(space.as_ref()) {
+                if occurrences[&name] > 1 {
+                    merged.push(format!("{}_{}", name, k + 1))
+                } else {
+                    merged.push(name)
+                }
+            }
+        }
+        merged
+    }
+
+    /// Gather the values of every factor for the i-th point of the product
+    fn merged_values(
+        &self,
+        i: usize,
+        values: impl Fn(&dyn SamplingSpace, usize) -> Vec<f64>,
+    ) -> Vec<f64> {
+        self.factors
+            .iter()
+            .zip(self.indices(i))
+            .flat_map(|(space, j)| values(space.as_ref(), j))
+            .collect()
+    }
+}
+
+impl SamplingSpace for ProductSpace {
+    fn torsion_names(&self) -> Vec<String> {
+        self.merged_names(|space| space.torsion_names())
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        self.merged_names(|space| space.coordinate_names())
+    }
+
+    fn len(&self) -> usize {
+        self.factors.iter().map(|space| space.len()).product()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        self.merged_values(i, |space, j| space.torsions(j))
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        self.merged_values(i, |space, j| space.coordinates(j))
+    }
+}
+
+/// Generate one factor of the product, with the settings of the other flags (sphere, regions,
+/// amplitude ...). The factors are streams, so no point of the product is computed before it is
+/// asked for
+pub fn factor(torsion_type: TorsionType, num: u64, flags: &Flags) -> Box<dyn SamplingSpace> {
+    let sub_flags = Flags {
+        torsion_type: Some(torsion_type),
+        num,
+        ..flags.clone()
+    };
+
+    match torsion_type {
+        TorsionType::Peptide => Box::new(peptide::peptide_stream(&sub_flags)),
+        TorsionType::Fivering => Box::new(fivering::fivering_stream(&sub_flags)),
+        TorsionType::Sixring => {
+            let stream = sixring::sixring_stream(&sub_flags);
+            stream.dropped.report();
+            Box::new(stream)
+        }
+        _ => panic!(
+            "`{:?}` can not be used in a product... Aborting.",
+            torsion_type
+        ),
+    }
+}
+
+/// Generate the Cartesian product of the prompted sampling spaces
+pub fn product(flags: &Flags) -> ProductSpace {
+    ProductSpace::new(
+        flags
+            .product
+            .iter()
+            .map(|(torsion_type, num)| factor(*torsion_type, *num, flags))
+            .collect(),
+    )
+}
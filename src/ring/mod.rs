@@ -0,0 +1,176 @@
+// import module(ring) modules
+pub mod puckering;
+pub mod reconstruct;
+pub mod sampling;
+
+use crate::arguments::Flags;
+use crate::parallel::map_indices;
+use crate::ring::puckering::{pair_count, PuckeringModes};
+use crate::ring::reconstruct::{close_ring, BondParameters};
+use crate::ring::sampling::puckering_grid;
+use crate::sixring::degeneracy::{Degeneracy, DegeneracySummary};
+use crate::sixring::geometry::{dihedral, Coordinate};
+use crate::torsion_typing::SamplingSpace;
+
+/// A typical total puckering amplitude Q (Angstrom) for an N-membered ring
+/// For rings of four and five atoms, this is the largest amplitude of the grid
+pub fn default_amplitude(size: usize) -> f64 {
+    match size {
+        4 => 0.25,
+        5 => 0.45,
+        6 => 0.67,
+        7 => 0.80,
+        _ => 1.00,
+    }
+}
+
+/// The N - 3 constrained torsion angles of a single point of the puckering grid, if the ring
+/// can be closed
+pub fn ring_torsions(
+    mode: &PuckeringModes,
+    bonds: &BondParameters,
+) -> Result<Vec<f64>, Degeneracy> {
+    close_ring(&mode.local_elevation(), &bonds.for_pucker(mode))
+        .map(|atoms| constrained_torsions(&atoms))
+}
+
+/// The sampling space of an N-membered ring
+///
+/// The pipeline mirrors the one of the sixring :
+///     puckering grid -> local elevation -> reconstruction -> dihedrals
+///
+/// A ring of N atoms needs N - 3 torsion angles to be constrained. These are the endocyclic
+/// torsions nu_0 .. nu_(N-4), where nu_j runs over the atoms (j, j+1, j+2, j+3).
+///
+/// Every conformation is generated once, when the stream is set up, since the points whose ring
+/// does not close are only known then. Only the puckering grid and the torsions are kept, so
+/// memory still grows with NUM, by one `PuckeringModes` and N - 3 torsions per point
+/// public `size` field : usize. N, the amount of atoms in the ring
+/// public `modes` field : Vec<PuckeringModes>. Only the points whose ring closes
+/// public `torsions` field : Vec<Vec<f64>>. The N - 3 torsion angles of every point
+/// public `dropped` field : DegeneracySummary
+pub struct RingStream {
+    pub size: usize,
+    pub modes: Vec<PuckeringModes>,
+    pub torsions: Vec<Vec<f64>>,
+    pub dropped: DegeneracySummary,
+}
+
+/// Generate the stream of torsion angles for N-membered rings. The points of the grid that can
+/// not be closed with the bond parameters are dropped, and counted
+pub fn ring_stream(size: usize, num: u64, amplitude: f64, bonds: &BondParameters) -> RingStream {
+    let grid = puckering_grid(size, num, amplitude);
+    let results = map_indices(grid.len(), |i| ring_torsions(&grid[i], bonds));
+
+    let mut modes = Vec::with_capacity(grid.len());
+    let mut torsions = Vec::with_capacity(grid.len());
+    let mut dropped = DegeneracySummary::new();
+    for (mode, result) in grid.into_iter().zip(results) {
+        match result {
+            Ok(nu) => {
+                modes.push(mode);
+                torsions.push(nu);
+            }
+            Err(reason) => dropped.add(reason),
+        }
+    }
+
+    RingStream {
+        size,
+        modes,
+        torsions,
+        dropped,
+    }
+}
+
+/// The bond parameters of an N-membered ring, with the bond lengths prompted with
+/// `--bond-length`. Either a single length for all bonds, or one for every bond
+pub fn prompted_bonds(flags: &Flags, size: usize, angle: f64) -> BondParameters {
+    let lengths = match &flags.bond_lengths {
+        None => BondParameters::default().lengths,
+        Some(l) if l.len() == 1 || l.len() == size => l.clone(),
+        Some(l) => panic!(
+            "{} bond lengths prompted for a ring of {} atoms, expected 1 or {}... Aborting.",
+            l.len(),
+            size,
+            size
+        ),
+    };
+    BondParameters { lengths, angle }
+}
+
+/// Generate the stream of torsion angles for the N-membered ring prompted with `--ring N NUM`
+pub fn nring_stream(flags: &Flags) -> RingStream {
+    let bonds = prompted_bonds(flags, flags.ring_size, BondParameters::default().angle);
+    ring_stream(
+        flags.ring_size,
+        flags.num,
+        flags
+            .amplitude
+            .unwrap_or(default_amplitude(flags.ring_size)),
+        &bonds,
+    )
+}
+
+impl SamplingSpace for RingStream {
+    /// NU0 .. NU(N-4)
+    fn torsion_names(&self) -> Vec<String> {
+        (0..self.size - 3).map(|j| format!("NU{}", j)).collect()
+    }
+
+    /// Q2, PHI2, Q3, PHI3 .. and Q(N/2) for even N
+    fn coordinate_names(&self) -> Vec<String> {
+        let mut columns = Vec::with_capacity(self.size - 3);
+        for k in 0..pair_count(self.size) {
+            columns.push(format!("Q{}", k + 2));
+            columns.push(format!("PHI{}", k + 2));
+        }
+        if self.size % 2 == 0 {
+            columns.push(format!("Q{}", self.size / 2));
+        }
+        columns
+    }
+
+    fn len(&self) -> usize {
+        self.modes.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        self.torsions[i].clone()
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        let mode = &self.modes[i];
+        let mut coordinates = Vec::with_capacity(self.size - 3);
+        for (q, phi) in mode.amplitudes.iter().zip(mode.phases.iter()) {
+            coordinates.push(*q);
+            coordinates.push(*phi);
+        }
+        if self.size % 2 == 0 {
+            coordinates.push(mode.amplitudes[pair_count(self.size)]);
+        }
+        coordinates
+    }
+}
+
+/// All N endocyclic torsion angles of the ring, nu_j over the atoms (j, j+1, j+2, j+3)
+pub fn endocyclic_torsions(atoms: &[Coordinate]) -> Vec<f64> {
+    let n = atoms.len();
+    (0..n)
+        .map(|j| {
+            dihedral(
+                atoms[j],
+                atoms[(j + 1) % n],
+                atoms[(j + 2) % n],
+                atoms[(j + 3) % n],
+            )
+        })
+        .collect()
+}
+
+/// The N - 3 torsion angles that need to be constrained to keep the ring's pucker
+pub fn constrained_torsions(atoms: &[Coordinate]) -> Vec<f64> {
+    let mut nu = endocyclic_torsions(atoms);
+    nu.truncate(atoms.len() - 3);
+    nu
+}
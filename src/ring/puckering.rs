@@ -0,0 +1,187 @@
+/// Puckering coordinates of an N-membered ring
+///
+///  " General definition of ring puckering coordinates, Cremer, DT and Pople, JA "
+///  Journal of the American Chemical Society. doi.org/10.1021/ja00839a011
+///
+/// An N-membered ring has N - 3 puckering coordinates :
+///
+/// ```text
+///     (q_m, phi_m)  for m = 2 .. (N - 1) / 2      : amplitude/phase pairs
+///     q_(N/2)                                     : a single amplitude, only for even N
+///
+///     N = 4 : q_2
+///     N = 5 : q_2, phi_2
+///     N = 6 : q_2, phi_2, q_3
+///     N = 7 : q_2, phi_2, q_3, phi_3
+///     N = 8 : q_2, phi_2, q_3, phi_3, q_4
+/// ```
+///
+/// The local elevation of atom j (j = 0 .. N) is the general form of the equation that is
+/// documented in `sixring/local_elevation.rs` :
+///
+/// ```text
+///     z_j = sqrt(2/N) * sum_m q_m * cos[phi_m + (2pi * m * j)/N]  +  1/sqrt(N) * q_(N/2) * (-1)^j
+/// ```
+///
+/// On a grid, the puckering coordinates are handled as a point in N - 3 dimensions, where
+/// every pair is written out in cartesian form (q_m cos(phi_m), q_m sin(phi_m)), followed by
+/// q_(N/2) for even N. The length of this vector is the total puckering amplitude Q.
+///
+/// The other way around, the puckering coordinates of a ring of atoms r_j follow from the
+/// elevations above its mean plane :
+///
+/// ```text
+///     R'  = sum_j r_j * sin(2pi * j / N)      R'' = sum_j r_j * cos(2pi * j / N)
+///     n   = (R' x R'') / |R' x R''|           z_j = (r_j - center) . n
+///
+///     q_m * cos(phi_m) =  sqrt(2/N) * sum_j z_j * cos(2pi * m * j / N)
+///     q_m * sin(phi_m) = -sqrt(2/N) * sum_j z_j * sin(2pi * m * j / N)
+///     q_(N/2)          =  1/sqrt(N) * sum_j z_j * (-1)^j
+/// ```
+///
+//
+// import modules
+use crate::sixring::equidistance_sphere::TWOPI;
+use crate::sixring::geometry::{Coordinate, LinAlg};
+
+/// The puckering coordinates of an N-membered ring
+/// public `size` field : usize. N, the amount of atoms in the ring
+/// public `amplitudes` field : Vec<f64>. q_2, q_3 .. ; the last one is q_(N/2) for even N
+/// public `phases` field : Vec<f64>. phi_2, phi_3 .. (radians), one for every pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct PuckeringModes {
+    pub size: usize,
+    pub amplitudes: Vec<f64>,
+    pub phases: Vec<f64>,
+}
+
+impl PuckeringModes {
+    /// Convert the cartesian form of the puckering coordinates to amplitudes and phases
+    pub fn from_cartesian(size: usize, x: &[f64]) -> PuckeringModes {
+        assert!(size >= 4, "A ring needs at least four atoms");
+        assert_eq!(
+            x.len(),
+            size - 3,
+            "A ring of N atoms has N - 3 puckering coordinates"
+        );
+
+        let pairs = pair_count(size);
+        let mut amplitudes = Vec::with_capacity(size / 2);
+        let mut phases = Vec::with_capacity(pairs);
+
+        for k in 0..pairs {
+            let (a, b) = (x[2 * k], x[2 * k + 1]);
+            amplitudes.push((a.powi(2) + b.powi(2)).sqrt());
+            phases.push(b.atan2(a).rem_euclid(TWOPI));
+        }
+
+        // the single amplitude of even membered rings, which can be negative
+        if size % 2 == 0 {
+            amplitudes.push(x[size - 4]);
+        }
+
+        PuckeringModes {
+            size,
+            amplitudes,
+            phases,
+        }
+    }
+
+    /// Analyse the puckering coordinates of a ring of atoms
+    pub fn from_atoms(atoms: &[Coordinate]) -> PuckeringModes {
+        let size = atoms.len();
+        let n = size as f64;
+
+        // the geometric center and the normal of the mean plane
+        let center = atoms
+            .iter()
+            .fold([0.; 3], |c, a| c.add_arr(a))
+            .scale_vector(1. / n);
+        let (mut r1, mut r2) = ([0.; 3], [0.; 3]);
+        for (j, a) in atoms.iter().enumerate() {
+            let r = a.subtract_arr(&center);
+            let angle = TWOPI * j as f64 / n;
+            r1 = r1.add_arr(&r.scale_vector(angle.sin()));
+            r2 = r2.add_arr(&r.scale_vector(angle.cos()));
+        }
+        let normal = r1.cross_product(&r2).normalise_vector();
+        let z: Vec<f64> = atoms
+            .iter()
+            .map(|a| a.subtract_arr(&center).dot_product(&normal))
+            .collect();
+
+        let mut x = Vec::with_capacity(size - 3);
+        for k in 0..pair_count(size) {
+            let m = (k + 2) as f64;
+            let (mut a, mut b) = (0., 0.);
+            for (j, zj) in z.iter().enumerate() {
+                let angle = TWOPI * m * j as f64 / n;
+                a += zj * angle.cos();
+                b -= zj * angle.sin();
+            }
+            x.push((2. / n).sqrt() * a);
+            x.push((2. / n).sqrt() * b);
+        }
+        if size % 2 == 0 {
+            let alternating = z
+                .iter()
+                .enumerate()
+                .map(|(j, zj)| zj * (-1_f64).powi(j as i32))
+                .sum::<f64>();
+            x.push(alternating / n.sqrt());
+        }
+
+        PuckeringModes::from_cartesian(size, &x)
+    }
+
+    /// Write the puckering coordinates back in their cartesian form
+    pub fn to_cartesian(&self) -> Vec<f64> {
+        let mut x = Vec::with_capacity(self.size - 3);
+        for (q, phi) in self.amplitudes.iter().zip(self.phases.iter()) {
+            x.push(q * phi.cos());
+            x.push(q * phi.sin());
+        }
+        if self.size % 2 == 0 {
+            x.push(*self.amplitudes.last().unwrap());
+        }
+        x
+    }
+
+    /// The total puckering amplitude Q
+    pub fn total_amplitude(&self) -> f64 {
+        self.amplitudes
+            .iter()
+            .map(|q| q.powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// The local elevation of every atom in the ring
+    pub fn local_elevation(&self) -> Vec<f64> {
+        let n = self.size as f64;
+        let pairs = pair_count(self.size);
+
+        (0..self.size)
+            .map(|j| {
+                // term 1 : the amplitude/phase pairs
+                let mut z: f64 = 0.;
+                for k in 0..pairs {
+                    let m = (k + 2) as f64;
+                    z += self.amplitudes[k] * (self.phases[k] + (TWOPI * m * j as f64) / n).cos();
+                }
+                z *= (2. / n).sqrt();
+
+                // term 2 : the single amplitude, for even membered rings
+                if self.size % 2 == 0 {
+                    z += self.amplitudes[pairs] * (-1_f64).powi(j as i32) / n.sqrt();
+                }
+                z
+            })
+            .collect()
+    }
+}
+
+/// The amount of amplitude/phase pairs of an N-membered ring
+pub fn pair_count(size: usize) -> usize {
+    (size - 3) / 2
+}
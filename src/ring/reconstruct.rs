@@ -0,0 +1,212 @@
+/// Rebuild the cartesian coordinates of an N-membered ring out of its local elevations
+///
+/// For the sixring, the ring is cut in triangles and the projected positions are derived
+/// analytically (see `sixring/ring_partition.rs` and `sixring/reconstruct_ring.rs`).
+/// Such a partition does not exist for every N, so here the projected (x, y) positions are
+/// fitted instead, while the local elevations (z) are kept fixed.
+///
+/// The fit starts from a regular polygon and minimises, with Levenberg-Marquardt :
+///
+/// ```text
+///     sum_j  w_bond  * ( |r_j - r_(j+1)|^2 - R_j^2     )^2    : the bond lengths
+///          + w_angle * ( |r_j - r_(j+2)|^2 - R_13,j^2  )^2    : the bond angles
+///
+///     where R_13,j^2 = R_j^2 + R_(j+1)^2 - 2 * R_j * R_(j+1) * cos(B_ijk)
+/// ```
+///
+/// Bond j runs from atom j to atom j+1. The bonds either share a single length, or each have
+/// their own.
+///
+/// A ring of N atoms has 2N - 3 projected degrees of freedom for 2N bond lengths and angles, so
+/// the bond lengths are weighted more heavily than the angles, which absorb the remainder.
+/// As the puckering modes of the elevations are orthogonal to the mean plane, the fitted ring
+/// has the same Cremer-Pople coordinates as the ones it was built from.
+///
+/// For large amplitudes, the elevations leave too little room in the mean plane and the fit
+/// settles on a ring that does not close with the prompted bond lengths. Such a ring is rejected
+/// when any of its bonds deviates more than `CLOSURE_TOLERANCE` from its length.
+///
+//
+// import modules
+use nalgebra::{DMatrix, DVector};
+use std::f64::consts::PI;
+
+use crate::ring::puckering::PuckeringModes;
+use crate::sixring::degeneracy::Degeneracy;
+use crate::sixring::geometry::{Coordinate, LinAlg};
+
+/// The bond parameters of the ring; its bond lengths (Angstrom) and bond angle (radians)
+/// public `lengths` field : Vec<f64>. A single length for all bonds, or one for every bond
+/// public `angle` field : f64
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondParameters {
+    pub lengths: Vec<f64>,
+    pub angle: f64,
+}
+
+impl Default for BondParameters {
+    /// The same values as the sixring; R_ij = 1.54 Angstrom and cos(B_ijk) = -1/3
+    fn default() -> Self {
+        BondParameters {
+            lengths: vec![1.54],
+            angle: (-1_f64 / 3.).acos(),
+        }
+    }
+}
+
+impl BondParameters {
+    /// The length of bond j, from atom j to atom j+1
+    pub fn length(&self, j: usize) -> f64 {
+        self.lengths[j % self.lengths.len()]
+    }
+
+    /// The mean bond length of a ring of `size` atoms
+    pub fn mean_length(&self, size: usize) -> f64 {
+        (0..size).map(|j| self.length(j)).sum::<f64>() / size as f64
+    }
+
+    /// The bond parameters to rebuild a ring of the given puckering with.
+    /// The angle of a four-membered ring follows from its pucker : a square of bond length R,
+    /// folded along its diagonals to the amplitude q2, has cos(B) = (q2 / R)^2
+    pub fn for_pucker(&self, modes: &PuckeringModes) -> BondParameters {
+        if modes.size != 4 {
+            return self.clone();
+        }
+        let ratio = modes.amplitudes[0] / self.mean_length(4);
+        BondParameters {
+            lengths: self.lengths.clone(),
+            angle: ratio.powi(2).min(1.).acos(),
+        }
+    }
+}
+
+const WEIGHT_BOND: f64 = 10.;
+const WEIGHT_ANGLE: f64 = 1.;
+const MAX_ITERATIONS: usize = 200;
+const TOLERANCE: f64 = 1e-12;
+
+/// The largest deviation (Angstrom) of a bond length for the fitted ring to count as closed
+pub const CLOSURE_TOLERANCE: f64 = 0.05;
+
+/// Return the coordinates of the ring atoms, as `reconstruct_ring`, if the fitted ring closes
+pub fn close_ring(z: &[f64], bonds: &BondParameters) -> Result<Vec<Coordinate>, Degeneracy> {
+    let atoms = reconstruct_ring(z, bonds);
+    if closure_residual(&atoms, bonds) > CLOSURE_TOLERANCE {
+        return Err(Degeneracy::Closure);
+    }
+    Ok(atoms)
+}
+
+/// The largest deviation of a bond length of the ring, the bond that closes it included
+pub fn closure_residual(atoms: &[Coordinate], bonds: &BondParameters) -> f64 {
+    let n = atoms.len();
+    (0..n)
+        .map(|j| (atoms[j].subtract_arr(&atoms[(j + 1) % n]).norm() - bonds.length(j)).abs())
+        .fold(0., f64::max)
+}
+
+/// Return the coordinates of the ring atoms, with the geometric center at the origin
+/// The atoms run clockwise when viewed from the top, like the reconstructed sixrings
+pub fn reconstruct_ring(z: &[f64], bonds: &BondParameters) -> Vec<Coordinate> {
+    let n = z.len();
+    let rij_sq: Vec<f64> = (0..n).map(|j| bonds.length(j).powi(2)).collect();
+    let r13_sq: Vec<f64> = (0..n)
+        .map(|j| {
+            let (a, b) = (bonds.length(j), bonds.length(j + 1));
+            a.powi(2) + b.powi(2) - 2. * a * b * bonds.angle.cos()
+        })
+        .collect();
+
+    // Initial guess : a regular polygon with the mean bond length
+    let radius = bonds.mean_length(n) / (2. * (PI / n as f64).sin());
+    let mut xy = DVector::<f64>::zeros(2 * n);
+    for j in 0..n {
+        let a = (PI / 2.) - (2. * PI * j as f64 / n as f64);
+        xy[2 * j] = radius * a.cos();
+        xy[2 * j + 1] = radius * a.sin();
+    }
+
+    let mut lambda: f64 = 1e-3;
+    let mut cost = residuals(&xy, z, &rij_sq, &r13_sq).norm_squared();
+
+    for _ in 0..MAX_ITERATIONS {
+        let r = residuals(&xy, z, &rij_sq, &r13_sq);
+        let jac = jacobian(&xy, n);
+
+        // damped normal equations; (J^T J + lambda * I) step = -J^T r
+        let jtj = jac.transpose() * &jac;
+        let jtr = jac.transpose() * &r;
+        let damped = &jtj + DMatrix::<f64>::identity(2 * n, 2 * n) * lambda;
+
+        let step = match damped.lu().solve(&(-jtr)) {
+            Some(s) => s,
+            None => break,
+        };
+
+        let candidate = &xy + &step;
+        let new_cost = residuals(&candidate, z, &rij_sq, &r13_sq).norm_squared();
+
+        if new_cost < cost {
+            let converged = (cost - new_cost) < TOLERANCE;
+            xy = candidate;
+            cost = new_cost;
+            lambda = (lambda / 10.).max(1e-12);
+            if converged {
+                break;
+            }
+        } else {
+            lambda *= 10.;
+            if lambda > 1e12 {
+                break;
+            }
+        }
+    }
+
+    // Move the geometric center to the origin
+    let cx = (0..n).map(|j| xy[2 * j]).sum::<f64>() / n as f64;
+    let cy = (0..n).map(|j| xy[2 * j + 1]).sum::<f64>() / n as f64;
+
+    (0..n)
+        .map(|j| [xy[2 * j] - cx, xy[2 * j + 1] - cy, z[j]])
+        .collect()
+}
+
+/// The weighted deviations of the 1-2 and 1-3 distances
+fn residuals(xy: &DVector<f64>, z: &[f64], rij_sq: &[f64], r13_sq: &[f64]) -> DVector<f64> {
+    let n = z.len();
+    let mut r = DVector::<f64>::zeros(2 * n);
+
+    for j in 0..n {
+        let (k, l) = ((j + 1) % n, (j + 2) % n);
+        r[j] = WEIGHT_BOND * (distance_sq(xy, z, j, k) - rij_sq[j]);
+        r[n + j] = WEIGHT_ANGLE * (distance_sq(xy, z, j, l) - r13_sq[j]);
+    }
+    r
+}
+
+/// The partial derivatives of the residuals to the projected positions
+fn jacobian(xy: &DVector<f64>, n: usize) -> DMatrix<f64> {
+    let mut jac = DMatrix::<f64>::zeros(2 * n, 2 * n);
+
+    for j in 0..n {
+        for (row, other, weight) in [
+            (j, (j + 1) % n, WEIGHT_BOND),
+            (n + j, (j + 2) % n, WEIGHT_ANGLE),
+        ] {
+            let dx = xy[2 * j] - xy[2 * other];
+            let dy = xy[2 * j + 1] - xy[2 * other + 1];
+            jac[(row, 2 * j)] = 2. * weight * dx;
+            jac[(row, 2 * j + 1)] = 2. * weight * dy;
+            jac[(row, 2 * other)] = -2. * weight * dx;
+            jac[(row, 2 * other + 1)] = -2. * weight * dy;
+        }
+    }
+    jac
+}
+
+/// The squared distance between atoms a and b
+fn distance_sq(xy: &DVector<f64>, z: &[f64], a: usize, b: usize) -> f64 {
+    (xy[2 * a] - xy[2 * b]).powi(2)
+        + (xy[2 * a + 1] - xy[2 * b + 1]).powi(2)
+        + (z[a] - z[b]).powi(2)
+}
@@ -0,0 +1,129 @@
+/// Sampling grids over the puckering coordinates of an N-membered ring
+///
+/// The grid depends on the dimensionality (D = N - 3) of the puckering space :
+///
+/// ```text
+///     D = 1 (N = 4)  : NUM values of q_2 in [-Q, Q]
+///     D = 2 (N = 5)  : NUM x NUM cartesian grid in [-Q, Q]^2, like the fivering axes
+///     D > 2 (N > 5)  : approx. NUM points on the hypersphere of radius Q,
+///                      i.e. at a fixed total puckering amplitude
+/// ```
+///
+/// The hypersphere is sampled with a generalisation of the Deserno scheme that is used for the
+/// sixring sphere (see `sixring/equidistance_sphere.rs`) : the surface of the hypersphere is
+/// divided in NUM patches of size d^(D-1). The first polar angle is cut up in layers spaced by
+/// d and every layer is a smaller hypersphere, which is sampled recursively with the same d.
+/// For D = 3, this comes down to the Deserno sphere.
+///
+//
+// import modules
+use std::f64::consts::PI;
+
+use ndarray::Array1;
+
+use crate::ring::puckering::PuckeringModes;
+use crate::sixring::equidistance_sphere::TWOPI;
+
+/// Generate the puckering coordinates to sample for an N-membered ring
+pub fn puckering_grid(size: usize, num: u64, amplitude: f64) -> Vec<PuckeringModes> {
+    let dimension = size - 3;
+
+    let points: Vec<Vec<f64>> = match dimension {
+        1 => Array1::linspace(-amplitude, amplitude, num as usize)
+            .iter()
+            .map(|q| vec![*q])
+            .collect(),
+        2 => {
+            let axis = Array1::linspace(-amplitude, amplitude, num as usize);
+            let mut grid = Vec::with_capacity(axis.len() * axis.len());
+            for x in axis.iter() {
+                for y in axis.iter() {
+                    grid.push(vec![*x, *y])
+                }
+            }
+            grid
+        }
+        _ => hypersphere(dimension, num, amplitude),
+    };
+
+    points
+        .iter()
+        .map(|x| PuckeringModes::from_cartesian(size, x))
+        .collect()
+}
+
+/// The surface area of the unit hypersphere S^(k) that lives in k + 1 dimensions
+///     A_0 = 2, A_1 = 2pi and A_k = A_(k-2) * 2pi / (k - 1)
+fn unit_sphere_area(k: usize) -> f64 {
+    match k {
+        0 => 2.,
+        1 => TWOPI,
+        _ => unit_sphere_area(k - 2) * TWOPI / (k - 1) as f64,
+    }
+}
+
+/// Approximately `num` equidistributed points on the hypersphere of the given radius
+/// in `dimension` dimensions. The points are returned in cartesian coordinates
+pub fn hypersphere(dimension: usize, num: u64, radius: f64) -> Vec<Vec<f64>> {
+    assert!(
+        dimension >= 2,
+        "A hypersphere lives in at least two dimensions"
+    );
+
+    // the edge of a patch of the unit hypersphere
+    let k = dimension - 1;
+    let d = (unit_sphere_area(k) / num as f64).powf(1. / k as f64);
+
+    let mut points = Vec::with_capacity(num as usize);
+    for unit in unit_hypersphere(k, d) {
+        points.push(unit.iter().map(|x| x * radius).collect())
+    }
+    points
+}
+
+/// Points on the unit hypersphere S^(k), spaced by approximately `d`
+/// The polar angle is taken from the last axis, so that the vector is (sin(psi) * S^(k-1), cos(psi))
+fn unit_hypersphere(k: usize, d: f64) -> Vec<Vec<f64>> {
+    if k == 1 {
+        // circle
+        let m_phi = (TWOPI / d).round().max(1.);
+        return (0..m_phi as usize)
+            .map(|n| {
+                let phi = TWOPI * n as f64 / m_phi;
+                vec![phi.cos(), phi.sin()]
+            })
+            .collect();
+    }
+
+    // After rounding the amount of layers, the spacing between the layers (d_psi) differs
+    // slightly from d. The spacing within a layer (d_sub) is corrected to keep the volume of
+    // a patch equal to d^k ; d_psi * d_sub^(k-1) = d^k
+    let m_psi = (PI / d).round().max(1.);
+    let d_psi = PI / m_psi;
+    let d_sub = (d.powi(k as i32) / d_psi).powf(1. / (k - 1) as f64);
+
+    let mut points = Vec::new();
+
+    for m in 0..m_psi as usize {
+        let psi = (PI * (m as f64 + 0.5)) / m_psi;
+
+        // close to the poles, a layer might not hold a single patch anymore
+        if unit_sphere_area(k - 1) * psi.sin().powi((k - 1) as i32)
+            < 0.5 * d_sub.powi((k - 1) as i32)
+        {
+            continue;
+        }
+
+        // every layer is a hypersphere of radius sin(psi), so the spacing on the unit
+        // hypersphere of that layer grows by a factor 1 / sin(psi)
+        let sub_d = d_sub / psi.sin();
+
+        for sub in unit_hypersphere(k - 1, sub_d) {
+            let mut p: Vec<f64> = sub.iter().map(|x| x * psi.sin()).collect();
+            p.push(psi.cos());
+            points.push(p);
+        }
+    }
+
+    points
+}
@@ -0,0 +1,59 @@
+use crate::arguments::Flags;
+use crate::ring::reconstruct::BondParameters;
+use crate::ring::{default_amplitude, prompted_bonds, ring_stream, RingStream};
+use crate::torsion_typing::Septanose;
+
+/// The total puckering amplitude Q to sample at; prompted or the default for septanoses
+pub fn sevenring_amplitude(flags: &Flags) -> f64 {
+    flags.amplitude.unwrap_or(default_amplitude(7))
+}
+
+/// The bond parameters of the septanose; the prompted bond lengths and a tetrahedral bond angle
+pub fn sevenring_bonds(flags: &Flags) -> BondParameters {
+    prompted_bonds(flags, 7, BondParameters::default().angle)
+}
+
+/// Generate the torsion angles to use as restraints for seven-membered rings
+///
+/// A seven-membered ring has four puckering coordinates (q2, phi2, q3, phi3). These are sampled
+/// on the hypersphere of a fixed total puckering amplitude Q :
+///     q2 = Q * sin(psi) ; q3 = Q * cos(psi)
+///
+/// The pipeline mirrors the one of the sixring :
+///     hypersphere -> local elevation -> reconstruction -> dihedrals
+///
+/// The four torsion angles nu0 .. nu3 that need to be constrained are returned.
+pub fn sevenring(flags: &Flags) -> Septanose {
+    let septanoses = sevenring_stream(flags);
+
+    let mut s = Septanose::new(septanoses.modes.len());
+
+    for (i, (modes, nu)) in septanoses
+        .modes
+        .iter()
+        .zip(&septanoses.torsions)
+        .enumerate()
+    {
+        s.nu0[i] = nu[0];
+        s.nu1[i] = nu[1];
+        s.nu2[i] = nu[2];
+        s.nu3[i] = nu[3];
+        s.q2[i] = modes.amplitudes[0];
+        s.phi2[i] = modes.phases[0];
+        s.q3[i] = modes.amplitudes[1];
+        s.phi3[i] = modes.phases[1];
+    }
+
+    // Dihedral function has values ORCA-ready
+    s
+}
+
+/// Generate the stream of torsion angles for seven-membered rings
+pub fn sevenring_stream(flags: &Flags) -> RingStream {
+    ring_stream(
+        7,
+        flags.num,
+        sevenring_amplitude(flags),
+        &sevenring_bonds(flags),
+    )
+}
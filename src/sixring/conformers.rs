@@ -0,0 +1,214 @@
+/// The canonical conformations of six-membered rings on the Cremer-Pople sphere
+///
+/// " Conformational nomenclature for five and six-membered ring forms of monosaccharides
+///   and their derivatives, IUPAC-IUB Joint Commission on Biochemical Nomenclature "
+/// European Journal of Biochemistry. doi.org/10.1111/j.1432-1033.1980.tb04463.x
+///
+/// The 38 forms are the 2 chairs (C), 6 boats (B), 6 skew-boats (S), 12 half-chairs (H)
+/// and 12 envelopes (E). Their position on the sphere is expressed in degrees.
+///
+/// ```text
+///     theta =   0.0           : 4C1
+///     theta =  50.8 /  54.7   : half-chairs / envelopes (northern hemisphere)
+///     theta =  90.0           : boats and skew-boats, every 30 degrees in phi
+///     theta = 125.3 / 129.2   : envelopes / half-chairs (southern hemisphere)
+///     theta = 180.0           : 1C4
+/// ```
+///
+/// Atoms are referred to by their position `j` in the ring (0..6), as used in the local
+/// elevation. The atoms written before the letter lie above the reference plane, the
+/// atoms written after the letter lie below it.
+/// By default, position 0 is the ring oxygen (O5) of a pyranose, followed by C1 to C5.
+///
+//
+// import modules
+use crate::sixring::sphere_regions::angular_distance;
+
+/// The default names of the ring atoms, for a pyranose numbered from the ring oxygen
+pub const DEFAULT_RING_ATOMS: [&str; 6] = ["O", "1", "2", "3", "4", "5"];
+
+/// The five families of six-membered ring conformations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformerKind {
+    Chair,
+    Boat,
+    SkewBoat,
+    HalfChair,
+    Envelope,
+}
+
+impl ConformerKind {
+    /// The letter used in the IUPAC nomenclature
+    pub fn letter(&self) -> &'static str {
+        match self {
+            ConformerKind::Chair => "C",
+            ConformerKind::Boat => "B",
+            ConformerKind::SkewBoat => "S",
+            ConformerKind::HalfChair => "H",
+            ConformerKind::Envelope => "E",
+        }
+    }
+}
+
+/// A canonical conformation, its position on the sphere (degrees) and its exoplanar atoms
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conformer {
+    pub kind: ConformerKind,
+    pub theta: f64,
+    pub phi: f64,
+    pub above: &'static [usize],
+    pub below: &'static [usize],
+}
+
+impl Conformer {
+    const fn new(
+        kind: ConformerKind,
+        theta: f64,
+        phi: f64,
+        above: &'static [usize],
+        below: &'static [usize],
+    ) -> Conformer {
+        Conformer {
+            kind,
+            theta,
+            phi,
+            above,
+            below,
+        }
+    }
+
+    /// Write out the IUPAC label of the conformation, with the given names of the ring atoms
+    /// e.g. `4C1`, `1,4B`, `OS2`, `4H3`, `E2`
+    pub fn label(&self, ring_atoms: &[String; 6]) -> String {
+        format!(
+            "{}{}{}",
+            atom_locants(self.above, ring_atoms),
+            self.kind.letter(),
+            atom_locants(self.below, ring_atoms)
+        )
+    }
+
+    /// The IUPAC label with the default pyranose numbering
+    pub fn default_label(&self) -> String {
+        self.label(&default_ring_atoms())
+    }
+}
+
+/// Return the default ring atom names as owned strings
+pub fn default_ring_atoms() -> [String; 6] {
+    DEFAULT_RING_ATOMS.map(|a| a.to_string())
+}
+
+/// Join the names of the exoplanar atoms; numbers first and in ascending order, then the others
+fn atom_locants(atoms: &[usize], ring_atoms: &[String; 6]) -> String {
+    let mut names: Vec<&String> = atoms.iter().map(|j| &ring_atoms[*j]).collect();
+
+    names.sort_by(|a, b| match (a.parse::<u32>(), b.parse::<u32>()) {
+        (Ok(x), Ok(y)) => x.cmp(&y),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    });
+
+    names
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// shorthand for the table below
+use ConformerKind::{Boat, Chair, Envelope, HalfChair, SkewBoat};
+
+/// All 38 canonical conformations of a six-membered ring
+pub const CANONICAL_CONFORMERS: [Conformer; 38] = [
+    // chairs
+    Conformer::new(Chair, 0., 0., &[4], &[1]),
+    Conformer::new(Chair, 180., 0., &[1], &[4]),
+    // boats
+    Conformer::new(Boat, 90., 0., &[0, 3], &[]),
+    Conformer::new(Boat, 90., 60., &[], &[1, 4]),
+    Conformer::new(Boat, 90., 120., &[2, 5], &[]),
+    Conformer::new(Boat, 90., 180., &[], &[0, 3]),
+    Conformer::new(Boat, 90., 240., &[1, 4], &[]),
+    Conformer::new(Boat, 90., 300., &[], &[2, 5]),
+    // skew-boats
+    Conformer::new(SkewBoat, 90., 30., &[3], &[1]),
+    Conformer::new(SkewBoat, 90., 90., &[5], &[1]),
+    Conformer::new(SkewBoat, 90., 150., &[2], &[0]),
+    Conformer::new(SkewBoat, 90., 210., &[1], &[3]),
+    Conformer::new(SkewBoat, 90., 270., &[1], &[5]),
+    Conformer::new(SkewBoat, 90., 330., &[0], &[2]),
+    // envelopes, northern hemisphere
+    Conformer::new(Envelope, 54.7, 0., &[0], &[]),
+    Conformer::new(Envelope, 54.7, 60., &[], &[1]),
+    Conformer::new(Envelope, 54.7, 120., &[2], &[]),
+    Conformer::new(Envelope, 54.7, 180., &[], &[3]),
+    Conformer::new(Envelope, 54.7, 240., &[4], &[]),
+    Conformer::new(Envelope, 54.7, 300., &[], &[5]),
+    // half-chairs, northern hemisphere
+    Conformer::new(HalfChair, 50.8, 30., &[0], &[1]),
+    Conformer::new(HalfChair, 50.8, 90., &[2], &[1]),
+    Conformer::new(HalfChair, 50.8, 150., &[2], &[3]),
+    Conformer::new(HalfChair, 50.8, 210., &[4], &[3]),
+    Conformer::new(HalfChair, 50.8, 270., &[4], &[5]),
+    Conformer::new(HalfChair, 50.8, 330., &[0], &[5]),
+    // envelopes, southern hemisphere
+    Conformer::new(Envelope, 125.3, 0., &[3], &[]),
+    Conformer::new(Envelope, 125.3, 60., &[], &[4]),
+    Conformer::new(Envelope, 125.3, 120., &[5], &[]),
+    Conformer::new(Envelope, 125.3, 180., &[], &[0]),
+    Conformer::new(Envelope, 125.3, 240., &[1], &[]),
+    Conformer::new(Envelope, 125.3, 300., &[], &[2]),
+    // half-chairs, southern hemisphere
+    Conformer::new(HalfChair, 129.2, 30., &[3], &[4]),
+    Conformer::new(HalfChair, 129.2, 90., &[5], &[4]),
+    Conformer::new(HalfChair, 129.2, 150., &[5], &[0]),
+    Conformer::new(HalfChair, 129.2, 210., &[1], &[0]),
+    Conformer::new(HalfChair, 129.2, 270., &[1], &[2]),
+    Conformer::new(HalfChair, 129.2, 330., &[3], &[2]),
+];
+
+/// Look up a canonical conformation by its IUPAC label (default pyranose numbering)
+/// The lookup is case-insensitive, so that `4c1` and `oh5` are found as well
+pub fn find_conformer(label: &str) -> Option<&'static Conformer> {
+    CANONICAL_CONFORMERS
+        .iter()
+        .find(|c| c.default_label().eq_ignore_ascii_case(label))
+}
+
+/// The nearest canonical conformation of a point on the sphere
+/// public `conformer` field : &Conformer
+/// public `distance` field : f64. The great-circle distance to the conformer, in degrees
+#[derive(Debug, Clone, Copy)]
+pub struct Classification {
+    pub conformer: &'static Conformer,
+    pub distance: f64,
+}
+
+/// Assign the nearest of the 38 canonical conformations to a point on the sphere (radians)
+pub fn classify(theta: f64, phi: f64) -> Classification {
+    let mut nearest = Classification {
+        conformer: &CANONICAL_CONFORMERS[0],
+        distance: f64::INFINITY,
+    };
+
+    for conformer in CANONICAL_CONFORMERS.iter() {
+        let d = angular_distance(
+            theta,
+            phi,
+            conformer.theta.to_radians(),
+            conformer.phi.to_radians(),
+        )
+        .to_degrees();
+
+        if d < nearest.distance {
+            nearest = Classification {
+                conformer,
+                distance: d,
+            }
+        }
+    }
+
+    nearest
+}
@@ -0,0 +1,95 @@
+/// Derive the puckering coordinates of an existing six-membered ring from its atom positions
+///
+///  " General definition of ring puckering coordinates, Cremer, DT and Pople, JA "
+///  Journal of the American Chemical Society. doi.org/10.1021/ja00839a011
+///
+/// ```text
+/// 1. Move the ring to its geometric center and define the mean plane
+///     R'  = sum_j r_j * sin(2pi * j / 6)
+///     R'' = sum_j r_j * cos(2pi * j / 6)
+///     n   = (R' x R'') / |R' x R''|
+///
+/// 2. The local elevation of every atom is its projection on the normal of the mean plane
+///     z_j = r_j . n
+///
+/// 3. The puckering coordinates, for N = 6
+///     q_2 * cos(phi_2) =  sqrt(2/6) * sum_j z_j * cos(4pi * j / 6)
+///     q_2 * sin(phi_2) = -sqrt(2/6) * sum_j z_j * sin(4pi * j / 6)
+///     q_3              =  sqrt(1/6) * sum_j z_j * (-1)^j
+///
+///     Q     = sqrt(q_2^2 + q_3^2)
+///     theta = atan2(q_2, q_3)
+///     phi   = phi_2
+/// ```
+///
+/// This is the inverse of the local elevation in `local_elevation.rs`.
+/// The atoms are expected in the order of the ring; running clockwise when viewed from the
+/// top face, like the rings built in `reconstruct_ring.rs`. Running the other way around
+/// mirrors theta to (pi - theta).
+///
+//
+// import modules
+use crate::sixring::conformers::{classify, Classification};
+use crate::sixring::equidistance_sphere::TWOPI;
+use crate::sixring::geometry::{Coordinate, LinAlg};
+use crate::sixring::local_elevation::Z_SIZE;
+
+/// The puckering coordinates of a six-membered ring
+/// public `q` field : f64. The total puckering amplitude, in Angstrom
+/// public `theta` field : f64. [0, pi]
+/// public `phi` field : f64. [0, 2pi]
+#[derive(Debug, Clone, Copy)]
+pub struct PuckeringCoordinates {
+    pub q: f64,
+    pub theta: f64,
+    pub phi: f64,
+}
+
+/// Calculate the Cremer-Pople coordinates of six ring atoms, given in the order of the ring
+pub fn cremer_pople(ring: &[Coordinate; Z_SIZE]) -> PuckeringCoordinates {
+    let n = Z_SIZE as f64;
+
+    // Move the ring to its geometric center
+    let center: Coordinate = [0, 1, 2].map(|i| ring.iter().map(|p| p[i]).sum::<f64>() / n);
+    let centered: Vec<Coordinate> = ring.iter().map(|p| p.subtract_arr(&center)).collect();
+
+    // The normal of the mean plane
+    let mut r1: Coordinate = [0., 0., 0.];
+    let mut r2: Coordinate = [0., 0., 0.];
+    for (j, r) in centered.iter().enumerate() {
+        let angle = TWOPI * j as f64 / n;
+        r1 = r1.add_arr(&r.scale_vector(angle.sin()));
+        r2 = r2.add_arr(&r.scale_vector(angle.cos()));
+    }
+    let normal = r1.cross_product(&r2).normalise_vector();
+
+    // Local elevations and their puckering modes
+    let z: Vec<f64> = centered.iter().map(|r| r.dot_product(&normal)).collect();
+
+    let mut q2_cos: f64 = 0.;
+    let mut q2_sin: f64 = 0.;
+    let mut q3: f64 = 0.;
+    for (j, zj) in z.iter().enumerate() {
+        let angle = 2. * TWOPI * j as f64 / n;
+        q2_cos += zj * angle.cos();
+        q2_sin -= zj * angle.sin();
+        q3 += zj * (-1_f64).powi(j as i32);
+    }
+    q2_cos *= (2. / n).sqrt();
+    q2_sin *= (2. / n).sqrt();
+    q3 /= n.sqrt();
+
+    let q2 = (q2_cos.powi(2) + q2_sin.powi(2)).sqrt();
+
+    PuckeringCoordinates {
+        q: (q2.powi(2) + q3.powi(2)).sqrt(),
+        theta: q2.atan2(q3),
+        phi: q2_sin.atan2(q2_cos).rem_euclid(TWOPI),
+    }
+}
+
+/// Assign the nearest canonical conformation to a six-membered ring
+pub fn classify_ring(ring: &[Coordinate; Z_SIZE]) -> Classification {
+    let puckering = cremer_pople(ring);
+    classify(puckering.theta, puckering.phi)
+}
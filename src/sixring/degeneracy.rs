@@ -0,0 +1,119 @@
+/// Not every point (rho, theta, phi) can be turned back into a sixring with fixed bond lengths
+/// and bond angles. For large puckering amplitudes, the local elevations (z_j) grow so large
+/// that the projections onto the mean plane can no longer be closed, and the square roots in the
+/// ring partitioning turn into NaN values. These NaN values would then silently end up as alpha
+/// dihedrals in the QM inputs.
+///
+/// The N-membered rings are fitted instead (see `ring/reconstruct.rs`). For large amplitudes,
+/// the fit still returns a ring, but one whose bonds no longer have their prompted lengths.
+///
+/// This module names the reasons why a reconstruction is impossible and keeps count of the
+/// conformations that were dropped because of it.
+//
+// import modules
+use std::fmt;
+
+/// The reason why a conformation can not be reconstructed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Degeneracy {
+    /// The difference in elevation of two bonded atoms exceeds the bond length,
+    /// so the projected bond length would be imaginary
+    /// ```text
+    /// R_{ij}^2 - (z_j - z_i)^2 < 0
+    /// ```
+    Elevation,
+    /// The projected bond angle has a cosine outside of [-1, 1]
+    BondAngle,
+    /// The triangle (O, P, Q) of the ring partitioning can not be closed
+    Triangle,
+    /// The fitted N-membered ring deviates from its bond lengths by more than the tolerance
+    /// ```text
+    /// max_j | |r_j - r_(j+1)| - R_j | > CLOSURE_TOLERANCE
+    /// ```
+    Closure,
+}
+
+impl Degeneracy {
+    /// A short description, used when reporting the dropped conformations
+    pub fn description(&self) -> &'static str {
+        match self {
+            Degeneracy::Elevation => "elevation difference exceeds the bond length",
+            Degeneracy::BondAngle => "projected bond angle out of range",
+            Degeneracy::Triangle => "ring partition can not be closed",
+            Degeneracy::Closure => "fitted ring does not close",
+        }
+    }
+}
+
+/// Keep count of the conformations that were dropped, per reason
+/// public `elevation` field : usize
+/// public `bond_angle` field : usize
+/// public `triangle` field : usize
+/// public `closure` field : usize
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DegeneracySummary {
+    pub elevation: usize,
+    pub bond_angle: usize,
+    pub triangle: usize,
+    pub closure: usize,
+}
+
+impl DegeneracySummary {
+    pub fn new() -> DegeneracySummary {
+        DegeneracySummary::default()
+    }
+
+    /// Count a dropped conformation
+    pub fn add(&mut self, reason: Degeneracy) {
+        match reason {
+            Degeneracy::Elevation => self.elevation += 1,
+            Degeneracy::BondAngle => self.bond_angle += 1,
+            Degeneracy::Triangle => self.triangle += 1,
+            Degeneracy::Closure => self.closure += 1,
+        }
+    }
+
+    /// The amount of conformations dropped for a given reason
+    pub fn count(&self, reason: Degeneracy) -> usize {
+        match reason {
+            Degeneracy::Elevation => self.elevation,
+            Degeneracy::BondAngle => self.bond_angle,
+            Degeneracy::Triangle => self.triangle,
+            Degeneracy::Closure => self.closure,
+        }
+    }
+
+    /// The total amount of dropped conformations
+    pub fn total(&self) -> usize {
+        self.elevation + self.bond_angle + self.triangle + self.closure
+    }
+
+    /// Print the summary to stderr, if any conformation was dropped
+    pub fn report(&self) {
+        if self.total() > 0 {
+            eprint!("{}", self)
+        }
+    }
+}
+
+impl fmt::Display for DegeneracySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Dropped {} conformation(s) that can not be reconstructed :",
+            self.total()
+        )?;
+        for reason in [
+            Degeneracy::Elevation,
+            Degeneracy::BondAngle,
+            Degeneracy::Triangle,
+            Degeneracy::Closure,
+        ] {
+            let count = self.count(reason);
+            if count > 0 {
+                writeln!(f, "  {:>8} : {}", count, reason.description())?;
+            }
+        }
+        Ok(())
+    }
+}
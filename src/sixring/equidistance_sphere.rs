@@ -2,6 +2,7 @@
 ///                 Mark Deserno, 2004, Max Planck Institute
 ///
 ///
+/// ```text
 ///     x = rho . sin(theta) . cos(phi)
 ///     y = rho . sin(theta) . sin(phi)
 ///     z = rho . cos(phi)
@@ -18,6 +19,7 @@
 ///
 ///
 ///     4 * PI * r^2 = surface area of a sphere
+/// ```
 ///
 //
 // import modules
@@ -33,8 +35,6 @@ pub fn equidistance_sphere(num: u64) -> SphericalAxes {
     let corrected_num: f64 = corrected_amount_of_points(num as f64);
     let a: f64 = (4. * PI * RHO.powi(2)) / corrected_num;
 
-    let mut idx: u32 = 0; // indexing the arrays
-
     // Set d as the square root of a
     let d: f64 = a.sqrt();
 
@@ -54,8 +54,7 @@ pub fn equidistance_sphere(num: u64) -> SphericalAxes {
         let m_phi: f64 = (TWOPI * globe.theta[m as usize].sin() / d_phi).round();
 
         for n in 0..m_phi as u32 {
-            globe.phi[idx as usize] = (TWOPI * n as f64) / m_phi;
-            idx += 1;
+            globe.push(m as usize, (TWOPI * n as f64) / m_phi);
         }
     }
     globe
@@ -53,7 +53,7 @@ impl LinAlg for Coordinate {
     }
 
     /// Normalise the size of the Coordinate
-    /// ```
+    /// ```text
     /// let d = 1. / self.norm();
     /// self.map(|x: f64| d * x) // apply the factor `d` to all elements of the coordinate
     /// ```
@@ -121,6 +121,7 @@ pub fn subtract_arr(a: Coordinate, b: Coordinate) -> Coordinate {
 /// Calculate the dihedral between four Coordinate points
 /// A dihedral is an angle between four points.
 /// Essentially :
+/// ```text
 ///     get three vector from the four points; b0, b1 and b2
 ///     from cross(b0, b1) and cross(b1, b2) we get two direction axes
 ///     -> The dot product between those to direction axes results in the dihedral angle
@@ -128,6 +129,7 @@ pub fn subtract_arr(a: Coordinate, b: Coordinate) -> Coordinate {
 ///     Here we use the praxeolitic formula, which involves 1 sqrt and 1 cross product
 ///     This does not use the description above, but it is more performant than this description
 ///     See : https://stackoverflow.com/questions/20305272/dihedral-torsion-angle-from-four-points-in-cartesian-coordinates-in-python
+/// ```
 ///
 /// Semantically, it makes little sense to implement the dihedral function as a method on
 /// the Coordinate type, as we need to add three more Coordinate variables to the function
@@ -154,6 +156,7 @@ pub fn dihedral(p0: Coordinate, p1: Coordinate, p2: Coordinate, p3: Coordinate)
 /// Custom trait to extend primitive type :
 /// Make extension trait on the primitive type `RotationMatrix`
 pub trait RotMatrix {
+    #[allow(clippy::new_ret_no_self)]
     fn new(phi: f64) -> RotationMatrix;
     fn apply_rotation(&self, p: Coordinate) -> Coordinate;
     fn apply_rotation_around_g(&self, p: Coordinate, idx: usize) -> f64;
@@ -185,3 +188,55 @@ impl RotMatrix for RotationMatrix {
         (self[idx][0] * p[0]) + (self[idx][1] * p[1]) + (self[idx][2] * p[2])
     }
 }
+
+/// The point (theta, phi) (radians) on the unit sphere
+pub fn unit_vector(theta: f64, phi: f64) -> Coordinate {
+    [
+        theta.sin() * phi.cos(),
+        theta.sin() * phi.sin(),
+        theta.cos(),
+    ]
+}
+
+/// The unit vectors along increasing theta and phi at a point on the sphere.
+/// These are defined at the poles as well, where they follow phi
+pub fn tangent_basis(theta: f64, phi: f64) -> [Coordinate; 2] {
+    [
+        [
+            theta.cos() * phi.cos(),
+            theta.cos() * phi.sin(),
+            -theta.sin(),
+        ],
+        [-phi.sin(), phi.cos(), 0.],
+    ]
+}
+
+/// The point at fraction t of the great-circle arc from a to b (theta, phi) (radians)
+/// ```text
+///     p(t) = ( sin((1 - t) d) p_a + sin(t d) p_b ) / sin(d)      d : the angle between a and b
+/// ```
+/// Opposite points have no single arc; the arc through phi of a is taken then.
+pub fn great_circle(a: [f64; 2], b: [f64; 2], t: f64) -> [f64; 2] {
+    let (pa, pb) = (unit_vector(a[0], a[1]), unit_vector(b[0], b[1]));
+    let cos = (0..3).map(|k| pa[k] * pb[k]).sum::<f64>().clamp(-1., 1.);
+    let d = cos.acos();
+
+    let p: Vec<f64> = if d < 1e-9 {
+        pa.to_vec()
+    } else if PI - d < 1e-9 {
+        // rotate a about the axis perpendicular to its meridian
+        let [e1, _] = tangent_basis(a[0], a[1]);
+        (0..3)
+            .map(|k| (t * PI).cos() * pa[k] + (t * PI).sin() * e1[k])
+            .collect()
+    } else {
+        (0..3)
+            .map(|k| (((1. - t) * d).sin() * pa[k] + (t * d).sin() * pb[k]) / d.sin())
+            .collect()
+    };
+
+    [
+        p[2].clamp(-1., 1.).acos(),
+        p[1].atan2(p[0]).rem_euclid(2. * PI),
+    ]
+}
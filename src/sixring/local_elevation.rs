@@ -1,10 +1,10 @@
 use crate::sixring::equidistance_sphere::TWOPI;
-use crate::torsion_typing::SphericalAxes;
 ///  Derive the local elevation of all the puckering modes and every mode's atomic elevation
 ///
 ///  " General definition of ring puckering coordinates, Cremer, DT and Pople, JA "
 ///  Journal of the American Chemical Society. doi.org/10.1021/ja00839a011
 ///
+/// ```text
 /// 1.        [                 term 1                         ]   [          term 2              ]
 ///     z_j = sqrt(2/N) * q_m * cos[phi_m + (2pi * m * (j-1))/N] + 1/sqrt(6) * q_(m+1) * (-1)^(j-1)
 ///
@@ -32,18 +32,12 @@ use crate::torsion_typing::SphericalAxes;
 /// -------
 /// LOCAL ELEVATION HAS BEEN CHECKED AND DEEMED CORRECT !
 /// -------
-// imports
-use ndarray::Array2;
-
+/// ```
 // CONSTANTS
 pub const Z_SIZE: usize = 6;
 
-pub fn cremerpople_evelation(sphere: &SphericalAxes) -> Array2<f64> {
-    // spherical coordinates are by default in radians
-
-    // 6 atomic elevations (Z_j) for any set of (r, theta, phi)
-    let mut z: Array2<f64> = Array2::zeros((sphere.amount, Z_SIZE));
-
+/// The 6 atomic elevations (Z_j) of a single conformation (r, theta, phi)
+pub fn local_elevation(rho: f64, theta: f64, phi: f64) -> [f64; Z_SIZE] {
     // Set two constant values
     let constant1 = constant_from_term1();
     let constant2 = constant_from_term2();
@@ -52,31 +46,17 @@ pub fn cremerpople_evelation(sphere: &SphericalAxes) -> Array2<f64> {
     let one_over_sqrt_three: f64 = 3_f64.sqrt();
     let one_over_sqrt_six: f64 = 6_f64.sqrt();
 
-    let mut idx_theta: usize = 0;
-    for i in 0..sphere.amount {
-        // the way we generate the sphere is in layered circles.
-        // every new circle, we start off again at phi == 0.0
-        // if we move to a new layer; we have to the next theta value
-        // NOTE :the theta and phi arrays are not of the same length
-        if (sphere.phi[i] == 0.0) && i != 0 {
-            idx_theta += 1
-        };
-
-        for j in 0..Z_SIZE {
-            z[[i, j]] = calculate_local_elevation(
-                sphere.rho,
-                sphere.theta[idx_theta],
-                sphere.phi[i],
-                constant1[j],
-                constant2[j],
-                one_over_sqrt_three,
-                one_over_sqrt_six,
-            );
-        }
-    }
-
-    // return local elevation of all the conformations
-    z
+    [0, 1, 2, 3, 4, 5].map(|j| {
+        calculate_local_elevation(
+            rho,
+            theta,
+            phi,
+            constant1[j],
+            constant2[j],
+            one_over_sqrt_three,
+            one_over_sqrt_six,
+        )
+    })
 }
 
 fn calculate_local_elevation(
@@ -95,7 +75,7 @@ fn calculate_local_elevation(
 }
 
 fn constant_from_term1() -> [f64; 6] {
-    [0., 1., 2., 3., 4., 5.].map(|j| ((TWOPI * j) / 3.))
+    [0., 1., 2., 3., 4., 5.].map(|j| (TWOPI * j) / 3.)
 }
 
 fn constant_from_term2() -> [f64; 6] {
@@ -110,6 +90,7 @@ mod test {
     use super::*;
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_iterating_over_array1() {
         // Rust method
         let vec1: Vec<f64> = vec![0., 1., 2., 3., 4., 5.]
@@ -125,13 +106,14 @@ mod test {
         }
 
         // Array method
-        let vec3: [f64; 6] = [0., 1., 2., 3., 4., 5.].map(|j| ((TWOPI * j) / 3.));
+        let vec3: [f64; 6] = [0., 1., 2., 3., 4., 5.].map(|j| (TWOPI * j) / 3.);
 
         assert_eq!(vec1.iter().sum::<f64>(), vec2.iter().sum());
         assert_eq!(vec2.iter().sum::<f64>(), vec3.iter().sum());
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_iterating_over_array2() {
         // Rust Vector method
         let vec1 = vec![0, 1, 2, 3, 4, 5]
@@ -1,39 +1,240 @@
 // import module(sixring) modules
+pub mod conformers;
+pub mod cremer_pople;
+pub mod degeneracy;
 pub mod equidistance_sphere;
 pub mod geometry;
 mod local_elevation;
 mod reconstruct_ring;
 mod ring_partition;
+pub mod sphere_distributions;
+pub mod sphere_regions;
 
-use crate::sixring::equidistance_sphere::equidistance_sphere;
+pub use reconstruct_ring::SixRingAtoms;
+
+use ndarray::Array2;
 
 use crate::arguments::Flags;
-use crate::sixring::ring_partition::RingPartition;
-use crate::torsion_typing::Pyranose;
+use crate::parallel::map_indices;
+use crate::sixring::conformers::classify;
+use crate::sixring::degeneracy::{Degeneracy, DegeneracySummary};
+use crate::sixring::reconstruct_ring::reconstruct_sixring;
+use crate::sixring::ring_partition::partition;
+use crate::sixring::sphere_distributions::great_circle_arc;
+use crate::sixring::sphere_regions::sphere_in_regions;
+use crate::torsion_typing::{
+    names, Label, LabelKind, Pyranose, SamplingSpace, SphericalAxes, PYRANOSE_COORDINATES,
+    PYRANOSE_TORSIONS,
+};
 
 use geometry::dihedral;
 
+/// Generate the points on the sphere to sample, restricted to the prompted regions,
+/// or along the prompted path. The radius of the sphere (rho) is the prompted amplitude, if any
+pub fn sampled_sphere(flags: &Flags) -> SphericalAxes {
+    let mut sphere = match flags.path {
+        Some(ends) => great_circle_arc(ends, flags.num),
+        None => sphere_in_regions(flags.sphere, flags.num, &flags.regions),
+    };
+    if let Some(rho) = flags.amplitude {
+        sphere.rho = rho;
+    }
+    sphere
+}
+
+/// Check whether the point (rho, theta, phi) on the sphere can be reconstructed into a sixring.
+/// Returns the reason why it can not, or None if it can
+pub fn degeneracy(rho: f64, theta: f64, phi: f64) -> Option<Degeneracy> {
+    sixring_conformation(rho, theta, phi).err()
+}
+
+/// Everything that is derived for a single sixring conformation
+/// public `elevation` field : [f64; 6]. The local elevation (z_j) of the conformation
+/// public `atoms` field : SixRingAtoms. The reconstructed ring
+/// public `alphas` field : [f64; 3]. The alpha dihedrals of the reconstructed ring
+pub struct SixringConformation {
+    pub elevation: [f64; 6],
+    pub atoms: SixRingAtoms,
+    pub alphas: [f64; 3],
+}
+
+/// Run the sixring pipeline on a single point (rho, theta, phi) on the sphere :
+///     local elevation -> projection and partition -> reconstruction -> alphas
+/// Every sixring conformation, streamed or collected, is generated through this function.
+/// Returns the reason why the point can not be reconstructed into a sixring, if so
+pub fn sixring_conformation(
+    rho: f64,
+    theta: f64,
+    phi: f64,
+) -> Result<SixringConformation, Degeneracy> {
+    let elevation = local_elevation::local_elevation(rho, theta, phi);
+    let projection = partition(&elevation);
+    if let Some(reason) = projection.degeneracy() {
+        return Err(reason);
+    }
+
+    let atoms = reconstruct_sixring(&projection, &elevation);
+    let alphas = alphas(&atoms);
+
+    // Dihedral function has values ORCA-ready
+    Ok(SixringConformation {
+        elevation,
+        atoms,
+        alphas,
+    })
+}
+
+/// The three alpha dihedrals of a reconstructed sixring
+fn alphas(pyr: &SixRingAtoms) -> [f64; 3] {
+    [
+        dihedral(pyr.p5, pyr.p1, pyr.p3, pyr.p2),
+        dihedral(pyr.p1, pyr.p3, pyr.p5, pyr.p4),
+        dihedral(pyr.p3, pyr.p5, pyr.p1, pyr.p6),
+    ]
+}
+
+/// Run the sixring pipeline once on every point of the sphere, and keep what `keep` takes out
+/// of every conformation. The points that can not be reconstructed are dropped from the sphere,
+/// and counted for why they were dropped
+fn generate_on_sphere<T, F>(
+    mut sphere: SphericalAxes,
+    keep: F,
+) -> (SphericalAxes, Vec<T>, DegeneracySummary)
+where
+    T: Send,
+    F: Fn(SixringConformation) -> T + Sync + Send,
+{
+    let rho = sphere.rho;
+    let results = map_indices(sphere.amount, |i| {
+        let point = sphere.points[i];
+        sixring_conformation(rho, point.theta, point.phi).map(&keep)
+    });
+
+    let mut dropped = DegeneracySummary::new();
+    let mut kept = Vec::with_capacity(results.len());
+    let mut reconstructed = Vec::with_capacity(results.len());
+    for result in results {
+        match result {
+            Ok(value) => {
+                kept.push(value);
+                reconstructed.push(true);
+            }
+            Err(reason) => {
+                dropped.add(reason);
+                reconstructed.push(false);
+            }
+        }
+    }
+
+    let mut reconstructed = reconstructed.into_iter();
+    sphere.points.retain(|_| reconstructed.next().unwrap());
+    sphere.amount = sphere.points.len();
+
+    (sphere, kept, dropped)
+}
+
+/// The pyranose sampling space. Every conformation is generated once, when the stream is set up,
+/// since the points that can not be reconstructed are only known then. Only the points on the
+/// sphere and their alphas are kept, so memory still grows with NUM, by one `SpherePoint` and
+/// three alphas per point
+/// public `sphere` field : SphericalAxes. Only the points that can be reconstructed
+/// public `alphas` field : Vec<[f64; 3]>. The alpha dihedrals of every point
+/// public `dropped` field : DegeneracySummary
+pub struct SixringStream {
+    pub sphere: SphericalAxes,
+    pub alphas: Vec<[f64; 3]>,
+    pub dropped: DegeneracySummary,
+}
+
+/// Generate the stream of torsion angles for pyranose molecules
+pub fn sixring_stream(flags: &Flags) -> SixringStream {
+    let (sphere, alphas, dropped) = generate_on_sphere(sampled_sphere(flags), |c| c.alphas);
+    SixringStream {
+        sphere,
+        alphas,
+        dropped,
+    }
+}
+
+/// Every conformation of the sixring, with every step of the pipeline kept
+/// public `sphere` field : SphericalAxes. The sampled points on the sphere
+/// public `elevations` field : Array2<f64>. The local elevation (z_j) of every conformation
+/// public `atoms` field : Vec<SixRingAtoms>. The reconstructed ring of every conformation
+/// public `pyranose` field : Pyranose. The alpha dihedrals of every conformation
+/// public `dropped` field : DegeneracySummary. The points that could not be reconstructed
+pub struct SixringGeneration {
+    pub sphere: SphericalAxes,
+    pub elevations: Array2<f64>,
+    pub atoms: Vec<SixRingAtoms>,
+    pub pyranose: Pyranose,
+    pub dropped: DegeneracySummary,
+}
+
+/// Generate the sixring conformations in a single pass, as the stream (and thus the command
+/// line) does, and keep every step of the pipeline for every conformation
+pub fn generate_sixring(flags: &Flags) -> SixringGeneration {
+    let (sphere, conformations, dropped) = generate_on_sphere(sampled_sphere(flags), |c| c);
+    let amount = sphere.amount;
+
+    let mut elevations = Array2::<f64>::zeros((amount, 6));
+    let mut p = Pyranose::new(amount);
+    p.rho = sphere.rho;
+    for (i, point) in sphere.iter().enumerate() {
+        p.theta[i] = point.theta;
+        p.phi[i] = point.phi;
+    }
+
+    let mut atoms = Vec::with_capacity(amount);
+    for (i, c) in conformations.into_iter().enumerate() {
+        for (j, z) in c.elevation.iter().enumerate() {
+            elevations[[i, j]] = *z;
+        }
+        [p.alpha1[i], p.alpha2[i], p.alpha3[i]] = c.alphas;
+        atoms.push(c.atoms);
+    }
+
+    SixringGeneration {
+        sphere,
+        elevations,
+        atoms,
+        pyranose: p,
+        dropped,
+    }
+}
+
 /// Calculate possible sampling space (spherical coordinates)
 pub fn sixring(flags: &Flags) -> Pyranose {
-    let sphere = equidistance_sphere(flags.num);
+    generate_sixring(flags).pyranose
+}
 
-    let projection =
-        local_elevation::cremerpople_evelation(&sphere).projection_and_partition(sphere.amount);
+impl SamplingSpace for SixringStream {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&PYRANOSE_TORSIONS)
+    }
 
-    let mut p = Pyranose::new(sphere.amount);
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&PYRANOSE_COORDINATES)
+    }
 
-    let vec_of_pyranoses = reconstruct_ring::reconstruct_coordinates(
-        &projection,
-        sphere.amount,
-        local_elevation::cremerpople_evelation(&sphere),
-    );
+    fn len(&self) -> usize {
+        self.sphere.amount
+    }
 
-    for (i, pyr) in vec_of_pyranoses.iter().enumerate() {
-        p.alpha1[i] = dihedral(pyr.p5, pyr.p1, pyr.p3, pyr.p2);
-        p.alpha2[i] = dihedral(pyr.p1, pyr.p3, pyr.p5, pyr.p4);
-        p.alpha3[i] = dihedral(pyr.p3, pyr.p5, pyr.p1, pyr.p6);
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        self.alphas[i].to_vec()
     }
 
-    // Dihedral function has values ORCA-ready
-    p
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        let point = self.sphere.points[i];
+        vec![self.sphere.rho, point.theta, point.phi]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Conformer)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        let point = self.sphere.points[i];
+        Some(Label::Conformer(classify(point.theta, point.phi)))
+    }
 }
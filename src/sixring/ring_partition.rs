@@ -1,5 +1,5 @@
+use crate::sixring::degeneracy::Degeneracy;
 use crate::sixring::local_elevation::Z_SIZE;
-use ndarray::{Array1, Array2, ArrayBase, DataOwned, Ix2};
 
 ///
 /// All six (6) bond length projections are needed
@@ -17,95 +17,98 @@ const RIJSQ: f64 = 1.54 * 1.54;
 const COSBIJK: f64 = -1. / 3.;
 //const BIJK : f64 = 1.9106332362490186 ; // around 109.4712206.. degrees, angle of perfect sp^3 angle
 
-// Make a trait where we can implement our own function on the ArrayBase<S,D> struct.
-pub trait RingPartition {
-    fn projection_and_partition(self, num: usize) -> ProjectionPartition;
+// The ring partitioning of a single conformation
+pub struct Partition {
+    pub rpij: [f64; Z_SIZE],
+    pub cosbpijk: [f64; Z_SIZE],
+    pub sinbpijk: [f64; Z_SIZE],
+    pub op: f64,
+    pub qp: f64,
+    pub oq: f64,
 }
 
-// Store ring partitioning in the struct
-pub struct ProjectionPartition {
-    pub rpij: Array2<f64>,
-    pub cosbpijk: Array2<f64>,
-    pub sinbpijk: Array2<f64>,
-    pub op: Array1<f64>,
-    pub qp: Array1<f64>,
-    pub oq: Array1<f64>,
+impl Partition {
+    /// Check whether the ring can be reconstructed out of this partitioning.
+    /// Returns the reason why it can not, or None if it can
+    pub fn degeneracy(&self) -> Option<Degeneracy> {
+        if self.rpij.iter().any(|r| !r.is_finite()) {
+            return Some(Degeneracy::Elevation);
+        }
+
+        if self.cosbpijk.iter().any(|c| !c.is_finite() || c.abs() > 1.) {
+            return Some(Degeneracy::BondAngle);
+        }
+
+        // The y-coordinate of P needs to be real, which is only the case when the triangle
+        // (O, P, Q) closes. Same expression as in the reconstruction
+        let pp_y = self.op.powi(2)
+            - ((self.op.powi(2) + self.oq.powi(2) - self.qp.powi(2)).powi(2)
+                / (4. * self.op.powi(2)));
+        if [self.op, self.qp, self.oq, pp_y]
+            .iter()
+            .any(|v| !v.is_finite() || *v < 0.)
+            || self.oq == 0.
+            || self.op == 0.
+        {
+            return Some(Degeneracy::Triangle);
+        }
+
+        None
+    }
 }
 
-impl<S> RingPartition for ArrayBase<S, Ix2>
-where
-    S: DataOwned<Elem = f64>, // Instead of having A as a generic type
-                              // we just need A to be f64 types
-                              // So we just prompt it in, since we won't use the function for
-                              // other type floats or integers
-{
-    /// The `self` parameter is actually the local_elevation matrix (z_j)
-    fn projection_and_partition(self, sphere_size: usize) -> ProjectionPartition {
-        let mut rpij_arr = Array2::<f64>::zeros((sphere_size as usize, Z_SIZE));
-        let mut cospb_arr = Array2::<f64>::zeros((sphere_size as usize, Z_SIZE));
-        let mut sinpb_arr = Array2::<f64>::zeros((sphere_size as usize, Z_SIZE));
-        let mut op_arr = Array1::<f64>::zeros(sphere_size as usize);
-        let mut qp_arr = Array1::<f64>::zeros(sphere_size as usize);
-        let mut oq_arr = Array1::<f64>::zeros(sphere_size as usize);
+/// The projections and partitioning of a single conformation, out of its local elevation (z_j)
+pub fn partition(z: &[f64; Z_SIZE]) -> Partition {
+    let mut rpij = [0_f64; Z_SIZE];
+    let mut cosbpijk = [0_f64; Z_SIZE];
+    let mut sinbpijk = [0_f64; Z_SIZE];
 
-        for i in 0..sphere_size as usize {
-            for j in 0..Z_SIZE {
-                rpij_arr[[i, j]] =
-                    (RIJSQ - (&self[[i, j]] - &self[[i, (j + 1) % Z_SIZE]]).powi(2)).sqrt();
-            }
+    for j in 0..Z_SIZE {
+        rpij[j] = (RIJSQ - (z[j] - z[(j + 1) % Z_SIZE]).powi(2)).sqrt();
+    }
 
-            for j in 0..Z_SIZE {
-                // sphere points are in radians
-                // the values of the cosine values are abnormal
-                // they all appear in values above 2PI and are often negative. This shouldnt be the
-                // case, where cosine values can only be between [-1 , 1]
-                cospb_arr[[i, j]] = (
-                    // zk - zi
-                    (&self[[i, (j+2) % Z_SIZE]] - &self[[i,j]]).powi(2)
-                    // zj - zi
-                    - (&self[[i, (j+1) % Z_SIZE]] - &self[[i,j]]).powi(2)
-                    // zk - zj
-                    - (&self[[i, (j+2) % Z_SIZE]] - &self[[i,(j+1) % Z_SIZE]]).powi(2)
-                    // 2 * rij * rjk * cos Bijk
-                    + (2. * RIJ * RIJ * COSBIJK)
-                    // 2 * rpij * rpjk
-                ) / (2. * rpij_arr[[i, j]] * rpij_arr[[i, (j + 1) % Z_SIZE]]);
+    for j in 0..Z_SIZE {
+        // For impossible conformations, the cosine values land outside of [-1, 1] and the
+        // sine turns into NaN. These are caught by `Partition::degeneracy()`
+        cosbpijk[j] = (
+            // zk - zi
+            (z[(j+2) % Z_SIZE] - z[j]).powi(2)
+            // zj - zi
+            - (z[(j+1) % Z_SIZE] - z[j]).powi(2)
+            // zk - zj
+            - (z[(j+2) % Z_SIZE] - z[(j+1) % Z_SIZE]).powi(2)
+            // 2 * rij * rjk * cos Bijk
+            + (2. * RIJ * RIJ * COSBIJK)
+            // 2 * rpij * rpjk
+        ) / (2. * rpij[j] * rpij[(j + 1) % Z_SIZE]);
 
-                sinpb_arr[[i, j]] = (1. - &cospb_arr[[i, j]].powi(2)).sqrt();
-            }
+        sinbpijk[j] = (1. - cosbpijk[j].powi(2)).sqrt();
+    }
 
-            op_arr[i] = ((rpij_arr[[i, 0]].powi(2) + rpij_arr[[i, 1]].powi(2))
-                - (2. * rpij_arr[[i, 0]] * rpij_arr[[i, 1]] * cospb_arr[[i, 0]]))
-            .sqrt();
-            qp_arr[i] = ((rpij_arr[[i, 2]].powi(2) + rpij_arr[[i, 3]].powi(2))
-                - (2. * rpij_arr[[i, 2]] * rpij_arr[[i, 3]] * cospb_arr[[i, 2]]))
-            .sqrt();
-            oq_arr[i] = ((rpij_arr[[i, 4]].powi(2) + rpij_arr[[i, 5]].powi(2))
-                - (2. * rpij_arr[[i, 4]] * rpij_arr[[i, 5]] * cospb_arr[[i, 4]]))
-            .sqrt();
-        }
+    let op = ((rpij[0].powi(2) + rpij[1].powi(2)) - (2. * rpij[0] * rpij[1] * cosbpijk[0])).sqrt();
+    let qp = ((rpij[2].powi(2) + rpij[3].powi(2)) - (2. * rpij[2] * rpij[3] * cosbpijk[2])).sqrt();
+    let oq = ((rpij[4].powi(2) + rpij[5].powi(2)) - (2. * rpij[4] * rpij[5] * cosbpijk[4])).sqrt();
 
-        /* C-Code
-         rpij[i]=sqrt(    rij[i] * rij[i] -
-                          (z[i]-z[(i+1)%N]) * (z[i]-z[(i+1)%N])
-                     );
+    /* C-Code
+     rpij[i]=sqrt(    rij[i] * rij[i] -
+                      (z[i]-z[(i+1)%N]) * (z[i]-z[(i+1)%N])
+                 );
 
-         COSbetapijk[i]= (  pow((z[(i+2)%N]-z[i]),2)-
-                            pow((z[(i+1)%N]-z[i]),2)-
-                            pow((z[(i+2)%N]-z[(i+1)%N]),2) +
-                            2.*rij[i]*rij[(i+1)%N]*cos(betaijk[i]) )
-                            /( 2.*rpij[i]*rpij[(i+1)%N] );
+     COSbetapijk[i]= (  pow((z[(i+2)%N]-z[i]),2)-
+                        pow((z[(i+1)%N]-z[i]),2)-
+                        pow((z[(i+2)%N]-z[(i+1)%N]),2) +
+                        2.*rij[i]*rij[(i+1)%N]*cos(betaijk[i]) )
+                        /( 2.*rpij[i]*rpij[(i+1)%N] );
 
-         SINbetapijk[i]= sqrt( 1- COSbetapijk[i]*COSbetapijk[i] );
-        */
+     SINbetapijk[i]= sqrt( 1- COSbetapijk[i]*COSbetapijk[i] );
+    */
 
-        ProjectionPartition {
-            rpij: rpij_arr,
-            cosbpijk: cospb_arr,
-            sinbpijk: sinpb_arr,
-            op: op_arr,
-            qp: qp_arr,
-            oq: oq_arr,
-        }
+    Partition {
+        rpij,
+        cosbpijk,
+        sinbpijk,
+        op,
+        qp,
+        oq,
     }
 }
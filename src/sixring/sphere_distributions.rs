@@ -0,0 +1,320 @@
+/// Alternative ways to distribute points on the surface of the puckering sphere
+///
+/// The Deserno scheme (see `equidistance_sphere.rs`) only approximates the prompted amount
+/// of points and places its first and last circle of latitude close to the poles, which gives
+/// ring-like artifacts around the chairs.
+///
+/// The following distributions are available as an alternative :
+///
+/// ```text
+///     Fibonacci   : golden spiral lattice. Returns exactly NUM points.
+///     HEALPix     : Hierarchical Equal Area isoLatitude Pixelisation, in nested indexing.
+///                   Returns 12 * nside^2 points, where nside is a power of two.
+///     Icosahedron : geodesic grid of a subdivided icosahedron.
+///                   Returns 10 * f^2 + 2 points, where f is the subdivision frequency.
+/// ```
+///
+/// HEALPix and the icosahedral grid are hierarchical : going up one level of refinement keeps
+/// all the points of the previous level (for the icosahedron, when doubling the frequency).
+/// Both pick the level whose amount of points lies closest to NUM.
+///
+/// Every distribution returns a `SphericalAxes` struct, where every point carries its own
+/// (theta, phi) value and the index of the layer it lives on.
+///
+/// Next to these, `great_circle_arc` places NUM points evenly along the great-circle arc between
+/// two points (e.g. two conformers), to run a relaxed scan from one pucker to the other.
+///
+//
+// import modules
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use ndarray::Array1;
+
+use crate::sixring::equidistance_sphere::{equidistance_sphere, RHO, TWOPI};
+use crate::sixring::geometry::{great_circle, Coordinate, LinAlg};
+use crate::torsion_typing::{SpherePoint, SphericalAxes};
+
+/// The ways to distribute points over the sphere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SphereDistribution {
+    Deserno,
+    Fibonacci,
+    Healpix,
+    Icosahedron,
+}
+
+impl SphereDistribution {
+    /// Parse the name of a distribution, as prompted on the command line
+    pub fn from_name(name: &str) -> Option<SphereDistribution> {
+        match name.to_lowercase().as_str() {
+            "deserno" | "equidistance" => Some(SphereDistribution::Deserno),
+            "fibonacci" | "golden" => Some(SphereDistribution::Fibonacci),
+            "healpix" => Some(SphereDistribution::Healpix),
+            "icosahedron" | "geodesic" => Some(SphereDistribution::Icosahedron),
+            _ => None,
+        }
+    }
+
+    /// Generate the points of the sphere, for approximately `num` points.
+    /// When no points are asked for, every distribution returns an empty sphere
+    pub fn generate(&self, num: u64) -> SphericalAxes {
+        if num == 0 {
+            return SphericalAxes::new(0, 0, RHO);
+        }
+
+        match self {
+            SphereDistribution::Deserno => equidistance_sphere(num),
+            SphereDistribution::Fibonacci => fibonacci_sphere(num),
+            SphereDistribution::Healpix => healpix_sphere(healpix_nside(num)),
+            SphereDistribution::Icosahedron => icosahedral_sphere(icosahedral_frequency(num)),
+        }
+    }
+}
+
+/// Golden spiral (Fibonacci) lattice on the sphere
+///
+/// Every point lives on its own circle of latitude :
+/// ```text
+///     z_i     = 1 - (2i + 1) / NUM
+///     theta_i = acos(z_i)
+///     phi_i   = i * golden_angle      (mod 2pi)
+/// ```
+pub fn fibonacci_sphere(num: u64) -> SphericalAxes {
+    let amount = num as usize;
+    let golden_angle: f64 = PI * (3. - 5_f64.sqrt());
+
+    let mut globe = SphericalAxes::new(amount, amount, RHO);
+
+    for i in 0..amount {
+        let z: f64 = 1. - ((2 * i + 1) as f64 / amount as f64);
+        globe.theta[i] = z.acos();
+        globe.push(i, (i as f64 * golden_angle) % TWOPI);
+    }
+
+    globe
+}
+
+/// Return the HEALPix resolution (a power of two) for which `12 * nside^2` is closest to `num`
+pub fn healpix_nside(num: u64) -> u64 {
+    let mut nside: u64 = 1;
+    while 12 * (2 * nside).pow(2) <= num {
+        nside *= 2;
+    }
+
+    // check whether the next resolution lies closer to the prompted amount
+    let lower = 12 * nside.pow(2);
+    let upper = 12 * (2 * nside).pow(2);
+    if num.saturating_sub(lower) > upper.saturating_sub(num) {
+        nside *= 2
+    }
+
+    nside
+}
+
+/// HEALPix pixel centers, in the nested indexing scheme
+///
+/// " HEALPix: a Framework for High Resolution Discretization and Fast Analysis of Data
+///   Distributed on the Sphere, Gorski, KM et al. "
+/// The Astrophysical Journal. doi.org/10.1086/427976
+///
+/// The index of a point is its nested pixel number, so that the four children of pixel `p`
+/// at resolution `2 * nside` are the pixels `4p`, `4p + 1`, `4p + 2` and `4p + 3`.
+/// The layers are the `4 * nside - 1` rings of constant latitude.
+pub fn healpix_sphere(nside: u64) -> SphericalAxes {
+    assert!(nside.is_power_of_two(), "`nside` has to be a power of two");
+
+    // The base resolution is made up of twelve faces, these hold the ring and the phi offset
+    const JRLL: [u64; 12] = [2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4];
+    const JPLL: [u64; 12] = [1, 3, 5, 7, 0, 2, 4, 6, 1, 3, 5, 7];
+
+    let npface = nside * nside;
+    let npix = 12 * npface;
+    let nl4 = 4 * nside;
+    let rings = (nl4 - 1) as usize;
+
+    let mut globe = SphericalAxes::new(npix as usize, rings, RHO);
+
+    for pix in 0..npix {
+        let face = (pix / npface) as usize;
+        let ipf = pix % npface;
+
+        // de-interleave the bits of the pixel index into the (ix, iy) position on the face
+        let ix = compress_bits(ipf);
+        let iy = compress_bits(ipf >> 1);
+
+        // ring number, counted from the north pole
+        let jr = JRLL[face] * nside - ix - iy - 1;
+
+        let (nr, z, kshift) = if jr < nside {
+            (jr, 1. - (jr * jr) as f64 / (3 * npface) as f64, 0)
+        } else if jr > 3 * nside {
+            let nr = nl4 - jr;
+            (nr, (nr * nr) as f64 / (3 * npface) as f64 - 1., 0)
+        } else {
+            let z = (2 * nside) as f64 - jr as f64;
+            (nside, z * 2. / (3 * nside) as f64, (jr - nside) & 1)
+        };
+
+        let mut jp = (JPLL[face] * nr + ix + 1 + kshift) as i64 - iy as i64;
+        jp /= 2;
+        if jp > nl4 as i64 {
+            jp -= nl4 as i64
+        };
+        if jp < 1 {
+            jp += nl4 as i64
+        };
+
+        let layer = (jr - 1) as usize;
+        globe.theta[layer] = z.acos();
+        globe.push(
+            layer,
+            (jp as f64 - (kshift + 1) as f64 * 0.5) * (PI / 2. / nr as f64),
+        );
+    }
+
+    globe
+}
+
+/// Take every other bit of an integer, starting from the least significant one
+fn compress_bits(v: u64) -> u64 {
+    let mut out: u64 = 0;
+    let mut i = 0;
+    while (v >> (2 * i)) != 0 {
+        out |= ((v >> (2 * i)) & 1) << i;
+        i += 1;
+    }
+    out
+}
+
+/// Return the subdivision frequency for which `10 * f^2 + 2` is closest to `num`
+pub fn icosahedral_frequency(num: u64) -> u64 {
+    let f = ((num.saturating_sub(2)) as f64 / 10.).sqrt().round() as u64;
+    f.max(1)
+}
+
+/// Geodesic grid, obtained by subdividing every face of an icosahedron in `f^2` triangles
+/// and projecting the new vertices on the sphere.
+///
+/// The icosahedron is oriented with a vertex on each pole, so that both chairs are sampled.
+/// The points are sorted from the north pole to the south pole and points of equal theta
+/// share a layer.
+pub fn icosahedral_sphere(frequency: u64) -> SphericalAxes {
+    let f = frequency.max(1);
+    let vertices = icosahedron_vertices();
+    let faces = icosahedron_faces();
+
+    // Collect the unique vertices of the subdivided triangles
+    // the key is the rounded cartesian coordinate, to discard shared edges and corners
+    let mut unique: HashMap<[i64; 3], Coordinate> = HashMap::new();
+
+    for face in faces.iter() {
+        let a = vertices[face[0]];
+        let b = vertices[face[1]];
+        let c = vertices[face[2]];
+        let ab = b.subtract_arr(&a);
+        let ac = c.subtract_arr(&a);
+
+        for i in 0..=f {
+            for j in 0..=(f - i) {
+                let p = a
+                    .add_arr(&ab.scale_vector(i as f64 / f as f64))
+                    .add_arr(&ac.scale_vector(j as f64 / f as f64))
+                    .normalise_vector();
+                let key = p.map(|x| (x * 1e9).round() as i64);
+                unique.entry(key).or_insert(p);
+            }
+        }
+    }
+
+    let mut points: Vec<(f64, f64)> = unique
+        .values()
+        .map(|p| {
+            let theta = p[2].clamp(-1., 1.).acos();
+            let phi = if p[0].abs() < 1e-12 && p[1].abs() < 1e-12 {
+                0. // the poles
+            } else {
+                p[1].atan2(p[0]).rem_euclid(TWOPI)
+            };
+            (theta, phi)
+        })
+        .collect();
+
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    layered_sphere(&points)
+}
+
+/// `num` points along the great-circle arc between two ends (theta, phi) (degrees), both included
+pub fn great_circle_arc(ends: [[f64; 2]; 2], num: u64) -> SphericalAxes {
+    let [from, to] = ends.map(|[theta, phi]| [theta.to_radians(), phi.to_radians()]);
+    let points: Vec<(f64, f64)> = (0..num)
+        .map(|i| {
+            let t = if num > 1 {
+                i as f64 / (num - 1) as f64
+            } else {
+                0.
+            };
+            let [theta, phi] = great_circle(from, to, t);
+            (theta, phi)
+        })
+        .collect();
+
+    layered_sphere(&points)
+}
+
+/// Build the `SphericalAxes` out of a list of (theta, phi) values that are sorted on theta
+/// Points with an equal theta value (within tolerance) are put on the same layer
+fn layered_sphere(points: &[(f64, f64)]) -> SphericalAxes {
+    let mut thetas: Vec<f64> = Vec::new();
+    let mut sphere_points: Vec<SpherePoint> = Vec::with_capacity(points.len());
+
+    for (theta, phi) in points.iter() {
+        match thetas.last() {
+            Some(last) if (theta - last).abs() < 1e-9 => (),
+            _ => thetas.push(*theta),
+        }
+        sphere_points.push(SpherePoint {
+            theta: *thetas.last().unwrap(),
+            phi: *phi,
+            layer: thetas.len() - 1,
+        });
+    }
+
+    SphericalAxes {
+        rho: RHO,
+        theta: Array1::from_vec(thetas),
+        points: sphere_points,
+        amount: points.len(),
+    }
+}
+
+/// The twelve vertices of an icosahedron, with a vertex on both poles
+fn icosahedron_vertices() -> [Coordinate; 12] {
+    let z: f64 = 1. / 5_f64.sqrt();
+    let r: f64 = 2. / 5_f64.sqrt();
+
+    let mut v: [Coordinate; 12] = [[0., 0., 0.]; 12];
+    v[0] = [0., 0., 1.];
+    for k in 0..5 {
+        let upper = TWOPI * k as f64 / 5.;
+        let lower = upper + (PI / 5.);
+        v[1 + k] = [r * upper.cos(), r * upper.sin(), z];
+        v[6 + k] = [r * lower.cos(), r * lower.sin(), -z];
+    }
+    v[11] = [0., 0., -1.];
+    v
+}
+
+/// The twenty faces of the icosahedron, as indices of `icosahedron_vertices()`
+fn icosahedron_faces() -> Vec<[usize; 3]> {
+    let mut faces = Vec::with_capacity(20);
+    for k in 0..5 {
+        let next = (k + 1) % 5;
+        faces.push([0, 1 + k, 1 + next]); // northern cap
+        faces.push([1 + k, 6 + k, 1 + next]); // upper band
+        faces.push([1 + next, 6 + k, 6 + next]); // lower band
+        faces.push([11, 6 + next, 6 + k]); // southern cap
+    }
+    faces
+}
@@ -0,0 +1,167 @@
+/// Restrict the sampling of the sixring sphere to a region of interest
+///
+/// Often only a part of the conformational space matters, e.g. the equator (boats and
+/// skew-boats) or the neighbourhood of the 4C1 chair.
+/// A region is prompted in degrees and is one of :
+///
+/// ```text
+///     theta:MIN,MAX            : a band of latitude,  e.g. theta:60,120 for the equator
+///     phi:MIN,MAX              : a band of longitude, e.g. phi:330,30 (wraps around 360)
+///     cap:THETA,PHI,RADIUS     : a spherical cap around (THETA, PHI), with an angular RADIUS
+///     conformer:LABEL,RADIUS   : a spherical cap around a canonical conformer, e.g. 4C1,30
+/// ```
+///
+/// When several regions are prompted, a point has to lie inside all of them.
+///
+/// To keep the density of points inside the region close to what was asked for, the sphere
+/// is generated with NUM / fraction points, where fraction is the part of the surface of the
+/// sphere that is covered by the region. Approximately NUM points are thus kept.
+///
+//
+// import modules
+use std::f64::consts::PI;
+
+use crate::sixring::conformers::find_conformer;
+use crate::sixring::sphere_distributions::{fibonacci_sphere, SphereDistribution};
+use crate::torsion_typing::SphericalAxes;
+
+const TO_RAD: f64 = PI / 180.;
+
+/// The amount of points used to estimate the surface covered by the regions
+const SURFACE_PROBES: u64 = 20_000;
+
+/// A region on the sphere. All angles are stored in radians
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SphereRegion {
+    ThetaBand { min: f64, max: f64 },
+    PhiBand { min: f64, max: f64 },
+    Cap { theta: f64, phi: f64, radius: f64 },
+}
+
+impl SphereRegion {
+    /// Parse a region as prompted on the command line, e.g. `cap:0,0,30`, `conformer:4C1,30`
+    /// or `conformer:B1,4,30`
+    pub fn from_query(query: &str) -> Result<SphereRegion, String> {
+        let (kind, raw) = match query.split_once(':') {
+            Some(kv) => kv,
+            None => return Err(format!("`{}` is not formatted as KIND:VALUES", query)),
+        };
+        let values: Vec<&str> = raw.split(',').map(|v| v.trim()).collect();
+
+        // parse the values that are expected to be numbers
+        let numbers = |from: usize| -> Result<Vec<f64>, String> {
+            values[from..]
+                .iter()
+                .map(|v| {
+                    v.parse::<f64>()
+                        .map_err(|_| format!("`{}` not parsed as a number", v))
+                })
+                .collect()
+        };
+
+        match (kind.to_lowercase().as_str(), values.len()) {
+            ("theta", 2) => {
+                let n = numbers(0)?;
+                Ok(SphereRegion::ThetaBand {
+                    min: n[0] * TO_RAD,
+                    max: n[1] * TO_RAD,
+                })
+            }
+            ("phi", 2) => {
+                let n = numbers(0)?;
+                Ok(SphereRegion::PhiBand {
+                    min: n[0].rem_euclid(360.) * TO_RAD,
+                    max: n[1].rem_euclid(360.) * TO_RAD,
+                })
+            }
+            ("cap", 3) => {
+                let n = numbers(0)?;
+                Ok(SphereRegion::Cap {
+                    theta: n[0] * TO_RAD,
+                    phi: n[1] * TO_RAD,
+                    radius: n[2] * TO_RAD,
+                })
+            }
+            // a boat label holds a comma itself (e.g. `B1,4`), the radius comes after the last one
+            ("conformer", n) if n >= 2 => {
+                let (label, radius) = raw.rsplit_once(',').unwrap_or((raw, ""));
+                let conformer = match find_conformer(label.trim()) {
+                    Some(c) => c,
+                    None => return Err(format!("`{}` is not a known conformer", label.trim())),
+                };
+                let radius = radius
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("`{}` not parsed as a number", radius.trim()))?;
+                Ok(SphereRegion::Cap {
+                    theta: conformer.theta * TO_RAD,
+                    phi: conformer.phi * TO_RAD,
+                    radius: radius * TO_RAD,
+                })
+            }
+            _ => Err(format!("`{}` is not a known region", query)),
+        }
+    }
+
+    /// Check if a point (radians) lies inside of the region
+    pub fn contains(&self, theta: f64, phi: f64) -> bool {
+        match *self {
+            SphereRegion::ThetaBand { min, max } => theta >= min && theta <= max,
+            SphereRegion::PhiBand { min, max } => {
+                if min <= max {
+                    phi >= min && phi <= max
+                } else {
+                    // the band wraps around phi = 0
+                    phi >= min || phi <= max
+                }
+            }
+            SphereRegion::Cap {
+                theta: t,
+                phi: p,
+                radius,
+            } => angular_distance(theta, phi, t, p) <= radius,
+        }
+    }
+}
+
+/// The great-circle distance (radians) between two points on the sphere
+///     cos(d) = cos(t1)cos(t2) + sin(t1)sin(t2)cos(p1 - p2)
+pub fn angular_distance(theta1: f64, phi1: f64, theta2: f64, phi2: f64) -> f64 {
+    let cos_d = (theta1.cos() * theta2.cos()) + (theta1.sin() * theta2.sin() * (phi1 - phi2).cos());
+    cos_d.clamp(-1., 1.).acos()
+}
+
+/// Estimate the fraction of the surface of the sphere that lies inside all the regions
+pub fn covered_fraction(regions: &[SphereRegion]) -> f64 {
+    let probes = fibonacci_sphere(SURFACE_PROBES);
+    let inside = probes
+        .iter()
+        .filter(|p| regions.iter().all(|r| r.contains(p.theta, p.phi)))
+        .count();
+
+    inside as f64 / probes.amount as f64
+}
+
+/// Generate the sphere with a refined density, and only keep the points inside the regions
+pub fn sphere_in_regions(
+    distribution: SphereDistribution,
+    num: u64,
+    regions: &[SphereRegion],
+) -> SphericalAxes {
+    if regions.is_empty() {
+        return distribution.generate(num);
+    }
+
+    let fraction = covered_fraction(regions);
+    if fraction == 0. {
+        panic!("The prompted regions do not cover any part of the sphere... Aborting.")
+    }
+
+    let mut sphere = distribution.generate((num as f64 / fraction).round() as u64);
+    sphere
+        .points
+        .retain(|p| regions.iter().all(|r| r.contains(p.theta, p.phi)));
+    sphere.amount = sphere.points.len();
+
+    sphere
+}
@@ -1,35 +1,36 @@
 //use std::f64::consts::PI;
 use ndarray::Array1;
 
-use crate::arguments::Flags;
-use crate::sixring::equidistance_sphere::equidistance_sphere;
-
-use anyhow::Result;
-use calm_io::stdoutln;
+use crate::fivering::pseudorotation::Pseudorotation;
+use crate::sixring::conformers::{classify, Classification};
 
 //const TO_RAD: f64 = PI / 180.;
 //const TO_DEG: f64 = 180. / PI ;
 // Which torsion type is going to be calculated
-//#[derive(Debug, Clone)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TorsionType {
     Peptide,
     Fivering,
     Sixring,
+    Sevenring,
+    Fourring,
+    Nucleoside,
+    Product,
+    Ring,
 }
 
-///
-/// Create structs to hold the torsion types in
-///
-/// The Peptide struct holds the data to iterate over the phi-psi backbone angles
-///
-/// The Furanose struct holds the data to iterate over the endocyclic dihedrals nu_1-nu_3
-///
-/// The Pyranose struct holds the data to iterate over the
-/// Strauss-Piccket alpha1-alpha2-alpha3 improper dihedrals
-///
-///
-///
+//
+// Create structs to hold the torsion types in
+//
+// The Peptide struct holds the data to iterate over the phi-psi backbone angles
+//
+// The Furanose struct holds the data to iterate over the endocyclic dihedrals nu_1-nu_3
+//
+// The Pyranose struct holds the data to iterate over the
+// Strauss-Piccket alpha1-alpha2-alpha3 improper dihedrals
+//
+//
+//
 
 //-------------
 //
@@ -58,9 +59,13 @@ impl Peptide {
 /// the `nu` dihedrals, according to the IUPAC nomenclature convention
 /// public `nu1` field : Array1<f64>
 /// public `nu3` field : Array1<f64>
+/// public `zx` field : Array1<f64>. The Zx coordinate of every point on the grid
+/// public `zy` field : Array1<f64>. The Zy coordinate of every point on the grid
 pub struct Furanose {
     pub nu1: Array1<f64>,
     pub nu3: Array1<f64>,
+    pub zx: Array1<f64>,
+    pub zy: Array1<f64>,
 }
 
 impl Furanose {
@@ -69,6 +74,8 @@ impl Furanose {
         Furanose {
             nu1: Array1::zeros(amount),
             nu3: Array1::zeros(amount),
+            zx: Array1::zeros(amount),
+            zy: Array1::zeros(amount),
         }
     }
 }
@@ -77,10 +84,16 @@ impl Furanose {
 /// public `alpha1` field : Array1<f64>
 /// public `alpha2` field : Array1<f64>
 /// public `alpha3` field : Array1<f64>
+/// public `rho` field : f64. The radius of the sphere
+/// public `theta` field : Array1<f64>. The theta coordinate of every point on the sphere
+/// public `phi` field : Array1<f64>. The phi coordinate of every point on the sphere
 pub struct Pyranose {
     pub alpha1: Array1<f64>,
     pub alpha2: Array1<f64>,
     pub alpha3: Array1<f64>,
+    pub rho: f64,
+    pub theta: Array1<f64>,
+    pub phi: Array1<f64>,
 }
 
 impl Pyranose {
@@ -90,6 +103,87 @@ impl Pyranose {
             alpha1: Array1::zeros(sphere_size),
             alpha2: Array1::zeros(sphere_size),
             alpha3: Array1::zeros(sphere_size),
+            rho: 0.,
+            theta: Array1::zeros(sphere_size),
+            phi: Array1::zeros(sphere_size),
+        }
+    }
+}
+
+/// the `nu1`, `nu3` dihedrals of the sugar and the glycosidic `chi` dihedral of a nucleoside
+/// public `nu1` field : Array1<f64>
+/// public `nu3` field : Array1<f64>
+/// public `chi` field : Array1<f64>
+/// public `zx` field : Array1<f64>. The Zx coordinate of the sugar of every point
+/// public `zy` field : Array1<f64>. The Zy coordinate of the sugar of every point
+pub struct Nucleoside {
+    pub nu1: Array1<f64>,
+    pub nu3: Array1<f64>,
+    pub chi: Array1<f64>,
+    pub zx: Array1<f64>,
+    pub zy: Array1<f64>,
+}
+
+impl Nucleoside {
+    /// Initialise the struct with an array of zeroes
+    pub fn new(amount: usize) -> Nucleoside {
+        Nucleoside {
+            nu1: Array1::zeros(amount),
+            nu3: Array1::zeros(amount),
+            chi: Array1::zeros(amount),
+            zx: Array1::zeros(amount),
+            zy: Array1::zeros(amount),
+        }
+    }
+}
+
+/// the single `nu` dihedral of a four-membered ring, over the ring atoms (0, 1, 2, 3)
+/// public `nu0` field : Array1<f64>
+/// public `q2` field : Array1<f64>. The puckering amplitude of every point
+pub struct Fourring {
+    pub nu0: Array1<f64>,
+    pub q2: Array1<f64>,
+}
+
+impl Fourring {
+    /// Initialise the struct with an array of zeroes
+    pub fn new(amount: usize) -> Fourring {
+        Fourring {
+            nu0: Array1::zeros(amount),
+            q2: Array1::zeros(amount),
+        }
+    }
+}
+
+/// the `nu` dihedrals of a seven-membered ring, where nu_j runs over ring atoms (j .. j+3)
+/// public `nu0` field : Array1<f64>
+/// public `nu1` field : Array1<f64>
+/// public `nu2` field : Array1<f64>
+/// public `nu3` field : Array1<f64>
+/// public `q2`, `phi2`, `q3`, `phi3` fields : Array1<f64>. The puckering coordinates of every point
+pub struct Septanose {
+    pub nu0: Array1<f64>,
+    pub nu1: Array1<f64>,
+    pub nu2: Array1<f64>,
+    pub nu3: Array1<f64>,
+    pub q2: Array1<f64>,
+    pub phi2: Array1<f64>,
+    pub q3: Array1<f64>,
+    pub phi3: Array1<f64>,
+}
+
+impl Septanose {
+    /// Initialise the struct with an array of zeroes
+    pub fn new(amount: usize) -> Septanose {
+        Septanose {
+            nu0: Array1::zeros(amount),
+            nu1: Array1::zeros(amount),
+            nu2: Array1::zeros(amount),
+            nu3: Array1::zeros(amount),
+            q2: Array1::zeros(amount),
+            phi2: Array1::zeros(amount),
+            q3: Array1::zeros(amount),
+            phi3: Array1::zeros(amount),
         }
     }
 }
@@ -139,149 +233,380 @@ impl FuranoseAxes {
     }
 }
 
+/// The periodic axis of the glycosidic torsion angle chi
+/// As 0 and 360 degrees are the same value, the last point of the axis is left out
+/// public `chi` field : Array1<f64>
+pub struct ChiAxis {
+    pub chi: Array1<f64>,
+}
+
+impl ChiAxis {
+    /// Initialise the struct with `num` evenly spaced values in [0, 360)
+    pub fn new(num: usize) -> ChiAxis {
+        ChiAxis {
+            chi: Array1::linspace(0., 360., num + 1)
+                .slice(ndarray::s![..num])
+                .to_owned(),
+        }
+    }
+}
+
+/// A single point on the sixring sphere
+/// public `theta` field : f64. [0, pi]
+/// public `phi` field : f64. [0, 2pi]
+/// public `layer` field : usize. The index of the circle of latitude the point lives on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpherePoint {
+    pub theta: f64,
+    pub phi: f64,
+    pub layer: usize,
+}
+
 /// The axes to iterate over for sixring molecules :
 /// public `rho` field : f64 . Standard value of 0.67
-/// public `theta` field : Array1<f64>. [0, pi] or [0, 180]
-/// public `phi` field : Array1<f64>. [0, 2pi] or [0, 360]
-/// public `amount` field : Array1<f64>. The corrected amount of points to sample
+/// public `theta` field : Array1<f64>. The theta value of every layer. [0, pi] or [0, 180]
+/// public `points` field : Vec<SpherePoint>. The (theta, phi) value of every point
+/// public `amount` field : usize. The corrected amount of points to sample
 pub struct SphericalAxes {
     pub rho: f64,
     pub theta: Array1<f64>,
-    pub phi: Array1<f64>,
+    pub points: Vec<SpherePoint>,
     pub amount: usize,
 }
 
 impl SphericalAxes {
+    /// Initialise the struct with zeroed layers and room for `amount` points
     pub fn new(amount: usize, m_theta: usize, rho: f64) -> SphericalAxes {
         SphericalAxes {
             rho,
             theta: Array1::<f64>::zeros(m_theta),
-            phi: Array1::<f64>::zeros(amount),
-            amount,
+            points: Vec::with_capacity(amount),
+            amount: 0,
         }
     }
+
+    /// Add a point on the given layer. The theta value of the layer has to be set beforehand
+    pub fn push(&mut self, layer: usize, phi: f64) {
+        self.points.push(SpherePoint {
+            theta: self.theta[layer],
+            phi,
+            layer,
+        });
+        self.amount = self.points.len();
+    }
+
+    /// Iterate over all the points of the sphere
+    pub fn iter(&self) -> std::slice::Iter<'_, SpherePoint> {
+        self.points.iter()
+    }
 }
 
-/// We implement the print to output method signature on Dihedrals,
-/// which will be implemented on Peptide, Furanose and Pyranose
-pub trait Dihedrals {
-    /// A `#` pound symbol is added by on the first line, to act as a comment symbol
-    /// for when one wants to easily parse it through numpy, shell scripts or as an easy identifier.
-    fn print_values(self, flags: Flags) -> Result<()>;
-}
-
-impl Dihedrals for Peptide {
-    fn print_values(self, flags: Flags) -> Result<()> {
-        let axis = PeptideAxes::new(flags.num as usize);
-
-        let amount: usize = flags.num as usize * flags.num as usize;
-        let num_f64 = flags.num as f64;
-        let mut x: f64;
-        let mut y: f64;
-
-        // header of output
-        println!(
-            "{} {} {} {}",
-            "#      PHI", "       PSI", "         X", "         Y"
-        );
-
-        for i in 0..amount {
-            x = (i as f64 / num_f64).floor();
-            y = i as f64 % num_f64;
-
-            match stdoutln!(
-                "{:width$.precision$} {:width$.precision$} {:width$.precision$} {:width$.precision$}",
-                     self.phi[i],
-                     self.psi[i],
-                     axis.x[x as usize],
-                     axis.y[y as usize],
-                     width=10, precision=3
-                ) {
-                    Ok(_)  => Ok(()),
-                    Err(e) => match e.kind() {
-                        std::io::ErrorKind::BrokenPipe => Ok(()),
-                        _ => Err(e),
-                    },
-            }?;
+//-------------
+//
+// Sampling spaces, which yield their points as records
+//
+//-------------
+//
+/// The kind of labels a sampling space can add to its points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelKind {
+    Conformer,
+    Pseudorotation,
+}
+
+/// The label of a single point
+/// `Conformer` : the nearest IUPAC conformer of a sixring and its distance
+/// `Pseudorotation` : the position of a fivering on the pseudorotation wheel
+#[derive(Debug, Clone, Copy)]
+pub enum Label {
+    Conformer(Classification),
+    Pseudorotation(Pseudorotation),
+}
+
+/// A single point of a sampling space
+/// public `index` field : usize. The index of the point in the sampling space
+/// public `torsions` field : Vec<f64>. The torsion angles, in the order of `torsion_names()`
+/// public `coordinates` field : Vec<f64>. The coordinates, in the order of `coordinate_names()`
+/// public `label` field : Option<Label>. Only set when the labels are requested
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub index: usize,
+    pub torsions: Vec<f64>,
+    pub coordinates: Vec<f64>,
+    pub label: Option<Label>,
+}
+
+/// A sampling space yields, for every point, a row of named torsion values and the coordinates
+/// of that point in the space it was sampled from.
+/// The points are handed out as `Sample` records, so writing them out is left to the caller
+/// (see `output.rs`). Sampling spaces can be combined (see `product/mod.rs`)
+///
+/// It is implemented on the structs that hold all values (Peptide, Furanose, Pyranose, ...) and
+/// on the streams of every generator (PeptideStream, FuranoseStream, SixringStream, ...), which
+/// only hold the axes and compute a point when it is asked for. The axes of the N-membered rings
+/// are their sampled points, and the sixring stream computes its points up front, so these
+/// still grow with NUM.
+/// Points can be asked for from several threads at once (see `parallel.rs`).
+pub trait SamplingSpace: Sync {
+    /// The names of the torsion angles, as they appear in the header of the output
+    fn torsion_names(&self) -> Vec<String>;
+
+    /// The names of the coordinates, as they appear in the header of the output
+    fn coordinate_names(&self) -> Vec<String>;
+
+    /// The amount of points in the sampling space
+    fn len(&self) -> usize;
+
+    /// The torsion angles of the i-th point
+    fn torsions(&self, i: usize) -> Vec<f64>;
+
+    /// The coordinates of the i-th point
+    fn coordinates(&self, i: usize) -> Vec<f64>;
+
+    /// The kind of labels the sampling space has, if any
+    fn label_kind(&self) -> Option<LabelKind> {
+        None
+    }
+
+    /// The label of the i-th point, if the sampling space has labels
+    fn label(&self, _i: usize) -> Option<Label> {
+        None
+    }
+
+    /// Whether the sampling space holds no points
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The i-th point as a record, with its label if requested
+    fn sample(&self, i: usize, labels: bool) -> Sample {
+        Sample {
+            index: i,
+            torsions: self.torsions(i),
+            coordinates: self.coordinates(i),
+            label: if labels { self.label(i) } else { None },
         }
+    }
+
+    /// Iterate over all points of the sampling space, with their labels if requested
+    /// For a `dyn SamplingSpace`, use `Samples::new()`
+    fn samples(&self, labels: bool) -> Samples<'_, Self>
+    where
+        Self: Sized,
+    {
+        Samples::new(self, labels)
+    }
+}
 
-        Ok(())
-    }
-}
-impl Dihedrals for Furanose {
-    fn print_values(self, flags: Flags) -> Result<()> {
-        let amount: usize = flags.num as usize * flags.num as usize;
-
-        let axis = FuranoseAxes::new(flags.num as usize);
-        let num_f64: f64 = flags.num as f64;
-
-        // header of output
-        println!(
-            "{} {} {} {}",
-            "#      NU1", "       NU3", "        Zx", "        Zy"
-        );
-
-        let mut x: f64;
-        let mut y: f64;
-
-        for i in 0..amount {
-            x = (i as f64 / num_f64).floor();
-            y = i as f64 % num_f64;
-
-            // https://github.com/Misterio77/flavours/commit/d958a604911b4a317e517c55e9cbc164e1d916fa#diff-adb9374a6a670def766e5e7c0118fd4132963182bc14e505950bf710c4ae47daR33
-            match stdoutln!(
-                "{:width$.precision$} {:width$.precision$} {:width$.precision$} {:width$.precision$}",
-                     self.nu1[i],
-                     self.nu3[i],
-                     axis.zx[x as usize],
-                     axis.zy[y as usize],
-                     width=10, precision=3
-                ) {
-                    Ok(_)  => Ok(()),
-                    Err(e) => match e.kind() {
-                        std::io::ErrorKind::BrokenPipe => Ok(()),
-                        _ => Err(e)
-                    },
-            }?;
+/// The iterator over the points of a sampling space
+pub struct Samples<'a, S: SamplingSpace + ?Sized> {
+    space: &'a S,
+    index: usize,
+    labels: bool,
+}
+
+impl<'a, S: SamplingSpace + ?Sized> Samples<'a, S> {
+    /// Start at the first point of the sampling space
+    pub fn new(space: &'a S, labels: bool) -> Samples<'a, S> {
+        Samples {
+            space,
+            index: 0,
+            labels,
         }
-        Ok(())
-    }
-}
-
-impl Dihedrals for Pyranose {
-    fn print_values(self, flags: Flags) -> Result<()> {
-        let axis = equidistance_sphere(flags.num);
-
-        // header of output
-        println!(
-            "{} {} {} {} {} {}",
-            "#   ALPHA1", "    ALPHA2", "    ALPHA3", "       RHO", "     THETA", "       PHI",
-        );
-
-        let mut it: usize = 0; // iterate over the theta array
-
-        for i in 0..axis.amount {
-            if (axis.phi[i] == 0.0) && i != 0 {
-                it += 1
-            }
-
-            match stdoutln!(
-                "{:width$.precision$} {:width$.precision$} {:width$.precision$} {:width$.precision$} {:width$.precision$} {:width$.precision$}",
-                         self.alpha1[i],
-                         self.alpha2[i],
-                         self.alpha3[i],
-                         axis.rho,
-                         axis.theta[it],
-                         axis.phi[i],
-                         width=10, precision=3
-                ) {
-                    Ok(_)  => Ok(()),
-                    Err(e) => match e.kind() {
-                        std::io::ErrorKind::BrokenPipe => Ok(()),
-                        _ => Err(e)
-                    },
-            }?;
+    }
+}
+
+impl<'a, S: SamplingSpace + ?Sized> Iterator for Samples<'a, S> {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Sample> {
+        if self.index >= self.space.len() {
+            return None;
         }
-        Ok(())
+        let sample = self.space.sample(self.index, self.labels);
+        self.index += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.space.len().saturating_sub(self.index);
+        (rest, Some(rest))
+    }
+}
+
+impl<'a, S: SamplingSpace + ?Sized> ExactSizeIterator for Samples<'a, S> {}
+
+/// The names of the torsion angles and the coordinates of the sampling spaces
+pub const PEPTIDE_TORSIONS: [&str; 2] = ["PHI", "PSI"];
+pub const PEPTIDE_COORDINATES: [&str; 2] = ["X", "Y"];
+pub const FURANOSE_TORSIONS: [&str; 2] = ["NU1", "NU3"];
+pub const FURANOSE_COORDINATES: [&str; 2] = ["Zx", "Zy"];
+pub const PYRANOSE_TORSIONS: [&str; 3] = ["ALPHA1", "ALPHA2", "ALPHA3"];
+pub const PYRANOSE_COORDINATES: [&str; 3] = ["RHO", "THETA", "PHI"];
+pub const NUCLEOSIDE_TORSIONS: [&str; 3] = ["NU1", "NU3", "CHI"];
+
+/// Turn a list of names into a list of Strings
+pub fn names(list: &[&str]) -> Vec<String> {
+    list.iter().map(|n| n.to_string()).collect()
+}
+
+impl SamplingSpace for Peptide {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&PEPTIDE_TORSIONS)
+    }
+
+    /// The backbone dihedrals are the axes of the grid themselves
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&PEPTIDE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.phi.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.phi[i], self.psi[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.phi[i], self.psi[i]]
+    }
+}
+
+impl SamplingSpace for Furanose {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&FURANOSE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&FURANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.nu1.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.nu1[i], self.nu3[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.zx[i], self.zy[i]]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Pseudorotation)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        Some(Label::Pseudorotation(Pseudorotation::from_zx_zy(
+            self.zx[i], self.zy[i],
+        )))
+    }
+}
+
+impl SamplingSpace for Pyranose {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&PYRANOSE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&PYRANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.alpha1.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.alpha1[i], self.alpha2[i], self.alpha3[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.rho, self.theta[i], self.phi[i]]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Conformer)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        Some(Label::Conformer(classify(self.theta[i], self.phi[i])))
+    }
+}
+
+impl SamplingSpace for Nucleoside {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&NUCLEOSIDE_TORSIONS)
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&FURANOSE_COORDINATES)
+    }
+
+    fn len(&self) -> usize {
+        self.chi.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.nu1[i], self.nu3[i], self.chi[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.zx[i], self.zy[i]]
+    }
+
+    fn label_kind(&self) -> Option<LabelKind> {
+        Some(LabelKind::Pseudorotation)
+    }
+
+    fn label(&self, i: usize) -> Option<Label> {
+        Some(Label::Pseudorotation(Pseudorotation::from_zx_zy(
+            self.zx[i], self.zy[i],
+        )))
+    }
+}
+
+impl SamplingSpace for Fourring {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&["NU0"])
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&["Q2"])
+    }
+
+    fn len(&self) -> usize {
+        self.nu0.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.nu0[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.q2[i]]
+    }
+}
+
+impl SamplingSpace for Septanose {
+    fn torsion_names(&self) -> Vec<String> {
+        names(&["NU0", "NU1", "NU2", "NU3"])
+    }
+
+    fn coordinate_names(&self) -> Vec<String> {
+        names(&["Q2", "PHI2", "Q3", "PHI3"])
+    }
+
+    fn len(&self) -> usize {
+        self.nu0.len()
+    }
+
+    fn torsions(&self, i: usize) -> Vec<f64> {
+        vec![self.nu0[i], self.nu1[i], self.nu2[i], self.nu3[i]]
+    }
+
+    fn coordinates(&self, i: usize) -> Vec<f64> {
+        vec![self.q2[i], self.phi2[i], self.q3[i], self.phi3[i]]
     }
 }
@@ -0,0 +1,96 @@
+use assert_float_eq::*;
+use ndarray::Array2;
+
+use puckers::arguments::Flags;
+use puckers::landscape::bicubic::{peptide_surface, write_cmap, PeriodicBicubic};
+use puckers::landscape::table::parse_table;
+
+/// A smooth periodic surface (in kcal/mol)
+fn surface(phi: f64, psi: f64) -> f64 {
+    let (phi, psi) = (phi.to_radians(), psi.to_radians());
+    2. + phi.cos() + 0.5 * (2. * psi).sin() + 0.3 * (phi - psi).cos()
+}
+
+/// A peptide landscape table of `num` x `num` points on the surface
+fn table(num: usize) -> String {
+    let mut text = String::from(
+        "#    INDEX        PHI        PSI          X          Y           ENERGY     STATUS\n",
+    );
+    let h = 360. / (num - 1) as f64;
+    for i in 0..num {
+        for j in 0..num {
+            let (phi, psi) = (i as f64 * h, j as f64 * h);
+            text.push_str(&format!(
+                "{:>10} {:10.3} {:10.3} {:10.3} {:10.3} {:16.8} {:>10}\n",
+                i * num + j,
+                phi,
+                psi,
+                phi,
+                psi,
+                surface(phi, psi) / 627.509474,
+                "ok"
+            ));
+        }
+    }
+    text
+}
+
+#[test]
+fn test_periodic_bicubic_interpolation() {
+    let n = 36;
+    let values = Array2::from_shape_fn((n, n), |(i, j)| surface(i as f64 * 10., j as f64 * 10.));
+    let bicubic = PeriodicBicubic::new(values);
+
+    // through the grid points, and close to the surface in between
+    assert_float_absolute_eq!(bicubic.evaluate(120., 250.), surface(120., 250.), 1e-10);
+    for &(phi, psi) in &[(-63.5, -41.2), (57.3, 47.9), (181.1, 359.9), (5., 355.)] {
+        assert_float_absolute_eq!(bicubic.evaluate(phi, psi), surface(phi, psi), 1e-4);
+    }
+
+    // periodic along both axes
+    assert_float_absolute_eq!(
+        bicubic.evaluate(-30., 400.),
+        bicubic.evaluate(330., 40.),
+        1e-12
+    );
+}
+
+#[test]
+fn test_peptide_surface_and_cmap() {
+    let flags = Flags::new();
+    let landscape = parse_table(&table(37)).unwrap();
+    let bicubic = peptide_surface(&landscape, &flags).unwrap();
+    assert_eq!(bicubic.values.nrows(), 36);
+
+    // relative to the global minimum of the grid
+    let minimum = bicubic.values.iter().cloned().fold(f64::INFINITY, f64::min);
+    assert_float_absolute_eq!(minimum, 0., 1e-9);
+
+    let mut out = Vec::new();
+    write_cmap(&bicubic, Some(&bicubic), 24, &flags, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "CMAP");
+    assert!(lines[2].ends_with("  24"));
+    assert_eq!(lines[4], "!phi = -180.0");
+    // 24 values per phi, five per line, and no correction against itself
+    assert_eq!(text.matches("!phi").count(), 24);
+    assert_eq!(lines[5].split_whitespace().count(), 5);
+    assert!(lines[5..10]
+        .iter()
+        .all(|l| l.split_whitespace().all(|v| v == "0.000000")));
+
+    // a grid point without energy can not be interpolated (the first one repeats at 360)
+    let incomplete: Vec<String> = table(13)
+        .lines()
+        .map(|line| {
+            if line.starts_with("        14 ") {
+                line.replace("        ok", "   missing")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    let incomplete = parse_table(&incomplete.join("\n")).unwrap();
+    assert!(peptide_surface(&incomplete, &flags).is_err());
+}
@@ -0,0 +1,48 @@
+use puckers::sixring::conformers::{classify, CANONICAL_CONFORMERS};
+use puckers::sixring::cremer_pople::{classify_ring, cremer_pople};
+use puckers::sixring::geometry::Coordinate;
+
+use assert_float_eq::*;
+
+/// Build a ring on a regular hexagon, displaced by the local elevation of (theta, phi)
+/// The atoms run clockwise, as in the reconstructed rings
+fn ring_from_sphere(theta: f64, phi: f64) -> [Coordinate; 6] {
+    let tau = 2. * std::f64::consts::PI;
+    [0, 1, 2, 3, 4, 5].map(|j| {
+        let a = -tau * j as f64 / 6.;
+        let z = 0.67
+            * (((1. / 3_f64).sqrt() * theta.sin() * (phi + (tau * j as f64 / 3.)).cos())
+                + ((1. / 6_f64).sqrt() * theta.cos() * (-1_f64).powi(j)));
+        [1.45 * a.cos(), 1.45 * a.sin(), z]
+    })
+}
+
+#[test]
+fn test_classify_canonical_positions() {
+    for conformer in CANONICAL_CONFORMERS.iter() {
+        let nearest = classify(conformer.theta.to_radians(), conformer.phi.to_radians());
+        assert_eq!(nearest.conformer, conformer);
+        assert_float_absolute_eq!(nearest.distance, 0., 0.001);
+    }
+}
+
+#[test]
+fn test_cremer_pople_roundtrip() {
+    let (theta, phi) = (1.2_f64, 4.0_f64);
+    let puckering = cremer_pople(&ring_from_sphere(theta, phi));
+
+    assert_float_absolute_eq!(puckering.q, 0.67, 0.001);
+    assert_float_absolute_eq!(puckering.theta, theta, 0.001);
+    assert_float_absolute_eq!(puckering.phi, phi, 0.001);
+
+    let chair = classify_ring(&ring_from_sphere(0.1, 0.));
+    assert_eq!(chair.conformer.default_label(), "4C1");
+}
+
+#[test]
+fn test_ring_atom_numbering() {
+    let c1_first = ["1", "2", "3", "4", "5", "O"].map(|a| a.to_string());
+    let nearest = classify(0., 0.);
+
+    assert_eq!(nearest.conformer.label(&c1_first), "5C2");
+}
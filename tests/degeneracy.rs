@@ -0,0 +1,43 @@
+use puckers::arguments::Flags;
+use puckers::sixring::degeneracy::Degeneracy;
+use puckers::sixring::{degeneracy, generate_sixring, sampled_sphere, sixring_stream};
+use puckers::torsion_typing::{SamplingSpace, TorsionType};
+
+#[test]
+fn test_default_amplitude_drops_nothing() {
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 630,
+        ..Flags::new()
+    };
+
+    let generation = generate_sixring(&flag);
+    assert_eq!(generation.dropped.total(), 0);
+    assert_eq!(generation.sphere.amount, generation.pyranose.alpha1.len());
+}
+
+#[test]
+fn test_extreme_amplitude_is_filtered() {
+    // the planar ring and the 4C1 chair are fine, a huge chair can not be closed anymore
+    assert_eq!(degeneracy(0., 0., 0.), None);
+    assert_eq!(degeneracy(0.67, 0., 0.), None);
+    assert_eq!(degeneracy(2.0, 0., 0.), Some(Degeneracy::Elevation));
+
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 500,
+        amplitude: Some(0.9),
+        ..Flags::new()
+    };
+
+    let stream = sixring_stream(&flag);
+    assert!(stream.dropped.total() > 0);
+    assert_eq!(
+        stream.dropped.total() + stream.len(),
+        sampled_sphere(&flag).amount
+    );
+
+    for sample in stream.samples(false) {
+        assert!(sample.torsions.iter().all(|t| t.is_finite()));
+    }
+}
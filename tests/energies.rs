@@ -0,0 +1,68 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::landscape::energies::{
+    boltzmann_populations, relative_energies, write_relative, EnergyUnit,
+};
+use puckers::landscape::table::parse_table;
+use puckers::torsion_typing::TorsionType;
+
+const TABLE: &str = "#    INDEX        PHI        PSI          X          Y           ENERGY     STATUS    OPT_PHI    OPT_PSI
+         0      0.000      0.000      0.000      0.000    -100.00000000         ok      0.000      0.000
+         1      0.000    180.000      0.000    180.000    -100.00159360         ok      0.000    180.000
+         2    180.000      0.000    180.000      0.000    -200.00000000     unconv    180.000      0.000
+         3    180.000    180.000    180.000    180.000              NaN    missing        NaN        NaN
+";
+
+#[test]
+fn test_relative_energies_and_populations() {
+    let energies = [-1.0, -1.001, f64::NAN];
+    let kcal = relative_energies(&energies, 1, EnergyUnit::KcalMol);
+    assert_float_absolute_eq!(kcal[0], 0.627509474, 1e-9);
+    assert_float_absolute_eq!(kcal[1], 0., 1e-9);
+    assert!(kcal[2].is_nan());
+
+    let ev = relative_energies(&energies, 1, EnergyUnit::from_name("eV").unwrap());
+    assert_float_absolute_eq!(ev[0], 0.027211386, 1e-8);
+
+    // two points at the same energy share the population
+    let populations = boltzmann_populations(&[-1.0, -1.0, f64::NAN], 298.15);
+    assert_float_absolute_eq!(populations[0], 0.5, 1e-12);
+    assert!(populations[2].is_nan());
+
+    // 1 kcal/mol at room temperature
+    let populations = boltzmann_populations(&[0., 1. / 627.509474], 298.15);
+    assert_float_absolute_eq!(populations[1] / populations[0], 0.1849, 1e-4);
+}
+
+#[test]
+fn test_write_relative_keeps_the_table() {
+    let table = parse_table(TABLE).unwrap();
+    assert_eq!(table.grid(), Some(TorsionType::Peptide));
+    assert_eq!(table.grid_columns(), vec!["PHI", "PSI"]);
+
+    let flags = Flags {
+        unit: EnergyUnit::KcalMol,
+        reference: Some(0),
+        temperature: Some(298.15),
+        ..Flags::new()
+    };
+    let mut out = Vec::new();
+    write_relative(&table, &flags, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert!(lines[0].ends_with("OPT_PSI   REL_KCAL        POP"));
+    assert!(lines[1].starts_with(table.rows[0].as_str()));
+    assert!(lines[1].ends_with("     0.000   0.156065"));
+    assert!(lines[2].ends_with("    -1.000   0.843935"));
+    // unconverged and missing jobs do not take part
+    assert!(lines[3].ends_with("       NaN        NaN"));
+    assert!(lines[4].ends_with("       NaN        NaN"));
+
+    let no_reference = Flags {
+        reference: Some(3),
+        ..Flags::new()
+    };
+    assert!(write_relative(&table, &no_reference, &mut Vec::new()).is_err());
+}
@@ -0,0 +1,110 @@
+use std::fs;
+
+use assert_float_eq::*;
+
+use puckers::landscape::gaussian::parse_gaussian;
+use puckers::landscape::xtb::{parse_constraints, parse_xtb};
+use puckers::landscape::{JobStatus, QmEngine};
+
+const GAUSSIAN_LOG: &str = " Entering Gaussian System, Link 0=g16
+ The following ModRedundant input section has been read:
+ D       1       2       3       4 F
+ D       6       5       3       2 F
+ GradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGrad
+ Berny optimization.
+ Initialization pass.
+                           ----------------------------
+                           !    Initial Parameters    !
+                           ! (Angstroms and Degrees)  !
+ --------------------------                            --------------------------
+ ! Name  Definition              Value          Derivative Info.                !
+ --------------------------------------------------------------------------------
+ ! R1    R(1,2)                  1.4300         estimate D2E/DX2                !
+ ! D1    D(1,2,3,4)            -60.0            Frozen                          !
+ ! D2    D(2,3,5,6)            180.0            Frozen                          !
+ --------------------------------------------------------------------------------
+ GradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGrad
+ SCF Done:  E(RB3LYP) =  -495.100000000     A.U. after   12 cycles
+ SCF Done:  E(RB3LYP) =  -495.123456780     A.U. after    8 cycles
+ Optimization completed.
+    -- Stationary point found.
+                           ----------------------------
+                           !   Optimized Parameters   !
+                           ! (Angstroms and Degrees)  !
+ --------------------------                            --------------------------
+ ! Name  Definition              Value          Derivative Info.                !
+ --------------------------------------------------------------------------------
+ ! R1    R(1,2)                  1.4312         -DE/DX =    0.0                 !
+ ! D1    D(1,2,3,4)            -60.0012         -DE/DX =    0.0                 !
+ ! D2    D(2,3,5,6)            179.9876         -DE/DX =    0.0                 !
+ ! D3    D(3,2,1,7)             10.0000         -DE/DX =    0.0                 !
+ --------------------------------------------------------------------------------
+ GradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGradGrad
+ Normal termination of Gaussian 16 at Mon Oct 19 12:00:00 2026.
+";
+
+#[test]
+fn test_parse_gaussian() {
+    let result = parse_gaussian(GAUSSIAN_LOG);
+    assert_eq!(result.status, JobStatus::Converged);
+    assert_eq!(result.energy, Some(-495.12345678));
+    // the second frozen dihedral is listed the other way around
+    assert_eq!(result.dihedrals, vec![-60.0012, 179.9876]);
+    assert_eq!(result.reason, None);
+
+    let stopped = GAUSSIAN_LOG.replace("Optimization completed.", "Optimization stopped.");
+    assert_eq!(parse_gaussian(&stopped).status, JobStatus::Unconverged);
+
+    let crashed = parse_gaussian(" Entering Gaussian System\n Error termination via Lnk1e\n");
+    assert_eq!(crashed.status, JobStatus::Crashed);
+    assert!(crashed.reason.unwrap().contains("error termination"));
+}
+
+#[test]
+fn test_parse_xtb_job() {
+    let output = "      |                           x T B                           |
+          | TOTAL ENERGY              -20.123456789012 Eh   |
+   * finished run on 2026/10/19 at 12:00:00.000
+           normal termination of xtb
+";
+    let result = parse_xtb(output);
+    assert_eq!(result.status, JobStatus::Converged);
+    assert_eq!(result.energy, Some(-20.123456789012));
+
+    let failed = parse_xtb(&output.replace(
+        "   * finished",
+        "FAILED TO CONVERGE GEOMETRY OPTIMIZATION\n",
+    ));
+    assert_eq!(failed.status, JobStatus::Unconverged);
+
+    let xcontrol = "$constrain\n   force constant=1.0\n   dihedral: 1,2,3,4,-60.0\n$end\n";
+    assert_eq!(parse_constraints(xcontrol), vec![[1, 2, 3, 4]]);
+
+    // a directory with the output, the optimised geometry and the detailed input
+    let root = std::env::temp_dir().join(format!("puckers-xtb-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("xtb.out"), output).unwrap();
+    fs::write(root.join("xcontrol.inp"), xcontrol).unwrap();
+    fs::write(
+        root.join("xtbopt.xyz"),
+        "4\n energy: -20.1234\nC 1.0 0.0 0.0\nC 0.0 0.0 0.0\nC 0.0 1.0 0.0\nC 0.0 1.0 1.0\n",
+    )
+    .unwrap();
+
+    let job = QmEngine::Xtb.parser().read_job(&root);
+    let missing = QmEngine::Xtb.parser().read_job(&root.join("nothing"));
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(job.dihedrals.len(), 1);
+    assert_float_absolute_eq!(job.dihedrals[0].abs(), 90., 0.001);
+    assert_eq!(missing.status, JobStatus::Missing);
+}
+
+#[test]
+fn test_engine_names() {
+    assert_eq!(QmEngine::from_name("ORCA"), Some(QmEngine::Orca));
+    assert_eq!(QmEngine::from_name("g16"), Some(QmEngine::Gaussian));
+    assert_eq!(QmEngine::from_name("xtb"), Some(QmEngine::Xtb));
+    assert_eq!(QmEngine::from_name("psi4"), None);
+    assert_eq!(QmEngine::Gaussian.parser().engine(), "Gaussian");
+}
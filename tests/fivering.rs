@@ -9,6 +9,7 @@ fn test_fivering_axes_generation() {
     let flag = Flags {
         torsion_type: Some(TorsionType::Fivering),
         num: 21,
+        ..Flags::new()
     };
 
     let fivering_axes = fivering(&flag);
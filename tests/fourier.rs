@@ -0,0 +1,75 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::landscape::fourier::{fivering_surface, FourierSurface};
+use puckers::landscape::table::parse_table;
+use puckers::landscape::SurfaceFit;
+
+/// A pseudorotation profile with a north/south preference, growing with the amplitude
+fn profile(phase: f64, amplitude: f64) -> f64 {
+    let p = phase.to_radians();
+    2. + 0.02 * amplitude * p.cos() - 0.001 * amplitude.powi(2) * (2. * p).sin()
+        + 0.001 * amplitude.powi(2)
+}
+
+#[test]
+fn test_fourier_fit_recovers_the_coefficients() {
+    let mut points = vec![];
+    for phase in (0..360).step_by(20) {
+        for amplitude in [10., 25., 40., 55.] {
+            points.push([phase as f64, amplitude]);
+        }
+    }
+    let values: Vec<f64> = points.iter().map(|p| profile(p[0], p[1])).collect();
+
+    let surface = FourierSurface::new(&points, &values, 2, 2).unwrap();
+    assert!(surface.residual_summary().1 < 1e-9);
+    assert_float_absolute_eq!(surface.evaluate(123., 33.), profile(123., 33.), 1e-9);
+
+    // (power, harmonic, cos, sin) with harmonic <= power : 1 + 3 + 5 coefficients
+    let terms = surface.terms();
+    assert_eq!(terms.len(), 6);
+    assert_eq!(surface.coefficients.len(), 9);
+    assert_float_absolute_eq!(terms[0].2, 2., 1e-9);
+    assert_float_absolute_eq!(terms[2].2, 0.02, 1e-9);
+    assert_float_absolute_eq!(terms[3].2, 0.001, 1e-9);
+    assert_float_absolute_eq!(terms[5].3, -0.001, 1e-9);
+
+    // the planar ring has a single fitted energy, whatever its phase
+    assert_float_absolute_eq!(surface.evaluate(0., 0.), surface.evaluate(200., 0.), 1e-12);
+
+    // too few points for the model, or a harmonic above the highest power
+    assert!(FourierSurface::new(&points[..8], &values[..8], 2, 2).is_err());
+    assert!(FourierSurface::new(&points, &values, 3, 2).is_err());
+}
+
+#[test]
+fn test_fivering_surface_from_a_table() {
+    let mut text = String::from("#    INDEX         Zx         Zy           ENERGY     STATUS\n");
+    let mut index = 0;
+    for zx in [-40., -20., 0., 20., 40.] {
+        for zy in [-40., -20., 0., 20., 40.] {
+            let e = -300. + 0.00001 * zx;
+            let status = if index == 7 { "crashed" } else { "ok" };
+            text.push_str(&format!(
+                "{:10} {:10.3} {:10.3} {:16.8} {:>10}\n",
+                index, zx, zy, e, status
+            ));
+            index += 1;
+        }
+    }
+    let table = parse_table(&text).unwrap();
+
+    let mut flags = Flags::new();
+    flags.fit = Some(SurfaceFit::Fourier(1, 1));
+    let (surface, rows) = fivering_surface(&table, &flags).unwrap();
+    assert_eq!(rows.len(), 24);
+    assert!(!rows.contains(&7));
+    // relative to the minimum at Zx = -40 : 0.00627509474 * (40 + A cos P) kcal/mol
+    assert_float_absolute_eq!(surface.coefficients[0], 0.251003790, 1e-6);
+    assert_float_absolute_eq!(surface.coefficients[2], 0.00627509474, 1e-8);
+
+    // a model on the sphere does not fit a fivering
+    flags.fit = Some(SurfaceFit::Harmonics(2));
+    assert!(fivering_surface(&table, &flags).is_err());
+}
@@ -0,0 +1,71 @@
+use puckers::arguments::Flags;
+use puckers::fourring::{fourring, fourring_bonds};
+use puckers::ring::reconstruct::reconstruct_ring;
+use puckers::ring::sampling::puckering_grid;
+use puckers::sixring::geometry::LinAlg;
+use puckers::torsion_typing::TorsionType;
+
+use assert_float_eq::*;
+
+#[test]
+fn test_fourring_axes_generation() {
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Fourring),
+        num: 11,
+        amplitude: Some(0.3),
+        bond_lengths: Some(vec![1.47]),
+        ..Flags::new()
+    };
+
+    let fourring_axes = fourring(&flag);
+    assert_eq!(fourring_axes.nu0.len(), 11);
+
+    // planar in the middle, mirrored on both sides and increasing along the axis
+    assert_float_absolute_eq!(fourring_axes.nu0[5], 0.0, 1e-6);
+    for i in 0..5 {
+        assert_float_absolute_eq!(fourring_axes.nu0[i], -fourring_axes.nu0[10 - i], 1e-6);
+        assert!(fourring_axes.nu0[i] < fourring_axes.nu0[i + 1]);
+    }
+
+    // the ring is rebuilt with the prompted bond length
+    let grid = puckering_grid(4, 11, 0.3);
+    let atoms = reconstruct_ring(&grid[0].local_elevation(), &fourring_bonds(&flag));
+    for j in 0..4 {
+        let r = atoms[j].subtract_arr(&atoms[(j + 1) % 4]).norm();
+        assert_float_absolute_eq!(r, 1.47, 0.02);
+    }
+}
+
+#[test]
+fn test_fourring_bonds_per_bond() {
+    let args = [
+        "puckers",
+        "--fourring",
+        "11",
+        "--bond-length",
+        "1.43,1.54,1.54,1.43",
+    ]
+    .map(String::from)
+    .to_vec();
+    let flag = Flags::return_cli_arguments(args);
+    let bonds = fourring_bonds(&flag);
+    assert_eq!(bonds.lengths, vec![1.43, 1.54, 1.54, 1.43]);
+
+    // every bond is rebuilt with its own length
+    let grid = puckering_grid(4, 11, 0.25);
+    let atoms = reconstruct_ring(&grid[1].local_elevation(), &bonds.for_pucker(&grid[1]));
+    for (j, expected) in bonds.lengths.iter().enumerate() {
+        let r = atoms[j].subtract_arr(&atoms[(j + 1) % 4]).norm();
+        assert_float_absolute_eq!(r, *expected, 0.02);
+    }
+
+    // the bond angle closes up as the ring puckers, cos(B) = (q2 / R)^2
+    let square = fourring_bonds(&Flags::new());
+    let puckered = square.for_pucker(&grid[0]);
+    assert_float_absolute_eq!(
+        puckered.angle.cos(),
+        (grid[0].amplitudes[0] / 1.54).powi(2),
+        1e-12
+    );
+    assert!(puckered.angle < square.angle);
+}
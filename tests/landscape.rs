@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+
+use puckers::arguments::Flags;
+use puckers::landscape::manifest::{parse_manifest, read_manifest};
+use puckers::landscape::orca::{parse_orca, Orca};
+use puckers::landscape::{collect_jobs, format_job, landscape_header, JobStatus};
+use puckers::peptide::peptide_stream;
+use puckers::torsion_typing::TorsionType;
+
+const ORCA_OUTPUT: &str = "
+                                 * O   R   C   A *
+                       *       Geometry Optimization Run       *
+        Definition                    Value    dE/dq     Step     New-Value
+     7. D(N   1,C   2,C   3,N   4)    -60.0000  0.000100  0.0000  -60.0000 C
+     8. D(C   0,N   1,C   2,C   3)    120.0000  0.000100  0.0000  120.0000 C
+     9. D(H   5,N   1,C   2,C   3)     10.0000  0.000100  0.0100   10.0100
+FINAL SINGLE POINT ENERGY      -495.123400000000
+                    ***        THE OPTIMIZATION HAS CONVERGED     ***
+            --- Optimized Parameters ---
+        Definition                    OldVal   dE/dq     Step     FinalVal
+     7. D(N   1,C   2,C   3,N   4)    -60.0000  0.000000  0.0000  -60.0012 C
+     8. D(C   0,N   1,C   2,C   3)    120.0000  0.000000  0.0000  119.9987 C
+FINAL SINGLE POINT ENERGY      -495.123456780000
+                             ****ORCA TERMINATED NORMALLY****
+";
+
+#[test]
+fn test_parse_orca() {
+    let result = parse_orca(ORCA_OUTPUT);
+    assert_eq!(result.status, JobStatus::Converged);
+    assert_eq!(result.energy, Some(-495.12345678));
+    assert_eq!(result.dihedrals, vec![-60.0012, 119.9987]);
+
+    // cut off before the optimization converged
+    let crashed = parse_orca(&ORCA_OUTPUT[..ORCA_OUTPUT.find("FINAL").unwrap()]);
+    assert_eq!(crashed.status, JobStatus::Crashed);
+    assert_eq!(crashed.energy, None);
+    assert_eq!(crashed.dihedrals, vec![-60.0, 120.0]);
+}
+
+#[test]
+fn test_parse_manifest() {
+    let text = "# INDEX DIRECTORY\n\n0 jobs/a\n  12   /abs/b \n";
+    let jobs = parse_manifest(text, Path::new("root")).unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0].index, 0);
+    assert_eq!(jobs[0].directory, Path::new("root/jobs/a"));
+    assert_eq!(jobs[1].directory, Path::new("/abs/b"));
+
+    assert!(parse_manifest("x jobs/a\n", Path::new("")).is_err());
+}
+
+#[test]
+fn test_collect_joins_grid_coordinates() {
+    let root = std::env::temp_dir().join(format!("puckers-collect-{}", std::process::id()));
+    fs::create_dir_all(root.join("job_4")).unwrap();
+    fs::write(root.join("job_4").join("slurm-1.out"), "not an ORCA output").unwrap();
+    fs::write(root.join("job_4").join("orca.out"), ORCA_OUTPUT).unwrap();
+    fs::write(root.join("manifest.txt"), "4 job_4\n5 job_5\n").unwrap();
+
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Peptide),
+        num: 3,
+        ..Flags::new()
+    };
+    let space = peptide_stream(&flags);
+    let jobs = read_manifest(root.join("manifest.txt").to_str().unwrap()).unwrap();
+    let results = collect_jobs(&jobs, &Orca);
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(results[0].status, JobStatus::Converged);
+    assert_eq!(results[1].status, JobStatus::Missing);
+
+    assert_eq!(
+        landscape_header(&space, &flags),
+        "#    INDEX        PHI        PSI          X          Y           ENERGY     STATUS    OPT_PHI    OPT_PSI"
+    );
+    assert_eq!(
+        format_job(&space, &jobs[0], &results[0], &flags),
+        "         4    180.000    180.000    180.000    180.000    -495.12345678         ok    -60.001    119.999"
+    );
+    assert!(format_job(&space, &jobs[1], &results[1], &flags).contains("NaN    missing"));
+}
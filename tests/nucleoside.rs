@@ -0,0 +1,32 @@
+use puckers::arguments::Flags;
+use puckers::fivering::fivering;
+use puckers::nucleoside::nucleoside;
+use puckers::torsion_typing::TorsionType;
+
+use assert_float_eq::*;
+
+#[test]
+fn test_nucleoside_axes_generation() {
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Nucleoside),
+        num: 5,
+        chi_num: Some(36),
+        ..Flags::new()
+    };
+
+    let nucleoside_axes = nucleoside(&flag);
+    let furanose_axes = fivering(&flag);
+
+    assert_eq!(nucleoside_axes.chi.len(), 25 * 36);
+
+    // chi is periodic, so 360 degrees is left out of the axis
+    assert_float_absolute_eq!(nucleoside_axes.chi[0], 0.0, 0.001);
+    assert_float_absolute_eq!(nucleoside_axes.chi[1], 10.0, 0.001);
+    assert_float_absolute_eq!(nucleoside_axes.chi[35], 350.0, 0.001);
+
+    // chi runs over the inner index, the sugar over the outer index
+    for i in [0, 35, 36, 400, 899] {
+        assert_float_absolute_eq!(nucleoside_axes.nu1[i], furanose_axes.nu1[i / 36], 0.001);
+        assert_float_absolute_eq!(nucleoside_axes.nu3[i], furanose_axes.nu3[i / 36], 0.001);
+    }
+}
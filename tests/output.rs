@@ -0,0 +1,58 @@
+use puckers::arguments::Flags;
+use puckers::fivering::fivering;
+use puckers::output::write_samples;
+use puckers::sixring::sixring;
+use puckers::torsion_typing::{Label, SamplingSpace, TorsionType};
+
+#[test]
+fn test_samples_without_output() {
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 100,
+        ..Flags::new()
+    };
+    let pyranose = sixring(&flags);
+
+    let samples: Vec<_> = pyranose.samples(true).collect();
+    assert_eq!(samples.len(), pyranose.len());
+
+    // the north pole is the 4C1 chair
+    let first = &samples[0];
+    assert_eq!(first.index, 0);
+    assert_eq!(
+        first.torsions,
+        vec![pyranose.alpha1[0], pyranose.alpha2[0], pyranose.alpha3[0]]
+    );
+    match first.label {
+        Some(Label::Conformer(nearest)) => assert_eq!(nearest.conformer.default_label(), "4C1"),
+        _ => panic!("a pyranose is labelled with its nearest conformer"),
+    }
+
+    // no labels unless requested
+    assert!(pyranose.samples(false).all(|s| s.label.is_none()));
+}
+
+#[test]
+fn test_write_samples() {
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Fivering),
+        num: 3,
+        labels: true,
+        ..Flags::new()
+    };
+
+    let mut buffer: Vec<u8> = Vec::new();
+    write_samples(&fivering(&flags), &flags, &mut buffer).unwrap();
+    let table = String::from_utf8(buffer).unwrap();
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 1 + 9);
+    assert_eq!(
+        lines[0],
+        "#      NU1        NU3         Zx         Zy          P       FORM             PUCKER SECTOR"
+    );
+    assert_eq!(
+        lines[5],
+        "     0.000     -0.000      0.000      0.000      0.000     planar             planar      -"
+    );
+}
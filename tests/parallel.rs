@@ -0,0 +1,28 @@
+use puckers::arguments::Flags;
+use puckers::output::write_samples;
+use puckers::parallel::map_indices;
+use puckers::sixring::{sixring, sixring_stream};
+use puckers::torsion_typing::TorsionType;
+
+#[test]
+fn test_map_indices_keeps_order() {
+    let squares = map_indices(100_000, |i| i * i);
+    assert!(squares.iter().enumerate().all(|(i, sq)| *sq == i * i));
+}
+
+#[test]
+fn test_array_and_stream_output_match() {
+    // more points than a single chunk of the output layer
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 5000,
+        ..Flags::new()
+    };
+
+    let mut from_arrays: Vec<u8> = Vec::new();
+    let mut from_stream: Vec<u8> = Vec::new();
+    write_samples(&sixring(&flags), &flags, &mut from_arrays).unwrap();
+    write_samples(&sixring_stream(&flags), &flags, &mut from_stream).unwrap();
+
+    assert_eq!(from_arrays, from_stream);
+}
@@ -0,0 +1,61 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::landscape::path::{minimum_energy_path, parse_path};
+use puckers::landscape::table::parse_table;
+use puckers::sixring::geometry::great_circle;
+use puckers::sixring::sphere_distributions::SphereDistribution;
+
+#[test]
+fn test_parse_path_ends() {
+    assert_eq!(parse_path("4C1:1C4").unwrap(), [[0., 0.], [180., 0.]]);
+    assert_eq!(parse_path("0,0:90,60").unwrap(), [[0., 0.], [90., 60.]]);
+    // a label with a comma is not a point
+    assert_eq!(parse_path("4c1:1,4B").unwrap()[1], [90., 240.]);
+    assert!(parse_path("4C1").is_err());
+    assert!(parse_path("4C1:XY").is_err());
+
+    // halfway between the pole and the equator
+    let [theta, phi] = great_circle([0., 1.], [std::f64::consts::FRAC_PI_2, 1.], 0.5);
+    assert_float_absolute_eq!(theta, std::f64::consts::FRAC_PI_4, 1e-12);
+    assert_float_absolute_eq!(phi, 1., 1e-12);
+}
+
+#[test]
+fn test_chair_to_chair_barrier() {
+    // two chairs at the poles, the lowest boats at phi = 60, 180 and 300 degrees
+    let mut text =
+        String::from("#    INDEX        RHO      THETA        PHI           ENERGY     STATUS\n");
+    let sphere = SphereDistribution::Fibonacci.generate(500);
+    for (i, p) in sphere.iter().enumerate() {
+        let e = -p.theta.cos().powi(2) + 0.3 * p.theta.sin().powi(2) * (3. * p.phi).cos();
+        text.push_str(&format!(
+            "{:10} {:10.3} {:10.6} {:10.6} {:16.8} {:>10}\n",
+            i,
+            0.67,
+            p.theta,
+            p.phi,
+            -200. + 0.001 * e,
+            "ok"
+        ));
+    }
+    let table = parse_table(&text).unwrap();
+
+    let mut flags = Flags::new();
+    let path = minimum_energy_path(&table, [[0., 0.], [180., 0.]], &flags).unwrap();
+    // E(boat) - E(chair) = 0.7 mHartree
+    let (forward, backward) = path.barriers();
+    assert_float_absolute_eq!(forward, 0.7e-3 * 627.509474, 0.02);
+    assert_float_absolute_eq!(backward, 0.7e-3 * 627.509474, 0.02);
+    let highest = path.highest().position;
+    assert_float_absolute_eq!(highest[0], std::f64::consts::FRAC_PI_2, 0.1);
+    assert_float_absolute_eq!((3. * highest[1]).cos(), -1., 0.05);
+    // a path over the sphere is at least half a great circle long
+    assert!(path.length >= std::f64::consts::PI);
+
+    // followed on the fitted surface, the path holds the points in between
+    flags.refine = true;
+    let refined = minimum_energy_path(&table, [[0., 0.], [180., 0.]], &flags).unwrap();
+    assert!(refined.profile.iter().any(|p| p.row.is_none()));
+    assert!(refined.barrier >= path.barrier - 0.01);
+}
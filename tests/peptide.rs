@@ -9,6 +9,7 @@ fn test_peptide_axes_generation() {
     let flag = Flags {
         torsion_type: Some(TorsionType::Peptide),
         num: 37,
+        ..Flags::new()
     };
 
     let peptide_axes = peptide(&flag);
@@ -0,0 +1,68 @@
+use puckers::arguments::Flags;
+use puckers::landscape::energies::EnergyUnit;
+use puckers::landscape::table::parse_table;
+use puckers::peptide::peptide_stream;
+use puckers::plot::{colour, render, PlotPoints, Projection};
+use puckers::sixring::sixring_stream;
+use puckers::torsion_typing::TorsionType;
+
+#[test]
+fn test_sixring_projections() {
+    let mut flags = Flags::new();
+    flags.torsion_type = Some(TorsionType::Sixring);
+    flags.num = 100;
+    let stream = sixring_stream(&flags);
+    let points = PlotPoints::from_space(&stream, TorsionType::Sixring).unwrap();
+
+    // every point and every conformer is drawn once, the equator twice on the polar discs
+    let hammer = render(&points, Projection::Hammer, "");
+    assert!(hammer.starts_with("<svg"));
+    assert!(hammer.ends_with("</svg>\n"));
+    assert_eq!(
+        hammer.matches("<circle").count(),
+        points.positions.len() + 38
+    );
+    for label in ["4C1", "1C4", "1,4B", "OS2"] {
+        assert!(hammer.contains(&format!(">{}</text>", label)));
+    }
+
+    let polar = render(&points, Projection::Polar, "");
+    assert!(polar.matches("<circle").count() > points.positions.len() + 38);
+    assert_eq!(Projection::from_name("Stoddart"), Some(Projection::Polar));
+    assert_eq!(Projection::from_name("robinson"), None);
+}
+
+#[test]
+fn test_peptide_heatmap() {
+    let mut flags = Flags::new();
+    flags.num = 4;
+    let stream = peptide_stream(&flags);
+    let grid = PlotPoints::from_space(&stream, TorsionType::Peptide).unwrap();
+    assert_eq!(grid.positions.len(), 16);
+
+    // a landscape with a failed job : one hollow cell, the lowest in dark blue
+    let mut text = String::from(
+        "#    INDEX        PHI        PSI          X          Y           ENERGY     STATUS\n",
+    );
+    for (i, p) in grid.positions.iter().enumerate() {
+        let status = if i == 5 { "crashed" } else { "ok" };
+        text.push_str(&format!(
+            "{:10} {:10.3} {:10.3} {:10.3} {:10.3} {:16.8} {:>10}\n",
+            i,
+            p[0],
+            p[1],
+            p[0],
+            p[1],
+            -100. + 0.001 * i as f64,
+            status
+        ));
+    }
+    let table = parse_table(&text).unwrap();
+    let landscape = PlotPoints::from_landscape(&table, EnergyUnit::KcalMol).unwrap();
+    let svg = render(&landscape, Projection::Hammer, "kcal/mol");
+
+    assert_eq!(svg.matches("fill=\"white\" stroke=\"none\"").count(), 1);
+    assert!(svg.contains(&format!("fill=\"{}\"", colour(0.))));
+    assert_eq!(colour(0.), "#440154");
+    assert_eq!(colour(1.), "#fde725");
+}
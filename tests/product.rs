@@ -0,0 +1,69 @@
+use puckers::arguments::Flags;
+use puckers::product::{factor, ProductSpace};
+use puckers::sixring::sphere_distributions::SphereDistribution;
+use puckers::torsion_typing::{SamplingSpace, TorsionType};
+
+use assert_float_eq::*;
+
+#[test]
+fn test_product_of_sampling_spaces() {
+    let flags = Flags {
+        sphere: SphereDistribution::Fibonacci,
+        ..Flags::new()
+    };
+
+    let space = ProductSpace::new(vec![
+        factor(TorsionType::Peptide, 3, &flags),
+        factor(TorsionType::Sixring, 10, &flags),
+    ]);
+
+    assert_eq!(space.len(), 9 * 10);
+    assert_eq!(space.indices(0), vec![0, 0]);
+    assert_eq!(space.indices(9), vec![0, 9]);
+    assert_eq!(space.indices(10), vec![1, 0]);
+    assert_eq!(space.indices(89), vec![8, 9]);
+
+    // `PHI` occurs in both spaces
+    assert_eq!(
+        space.torsion_names(),
+        vec!["PHI_1", "PSI", "ALPHA1", "ALPHA2", "ALPHA3"]
+    );
+    assert_eq!(
+        space.coordinate_names(),
+        vec!["X", "Y", "RHO", "THETA", "PHI_2"]
+    );
+
+    // the rows are the merged rows of both spaces
+    let peptide = factor(TorsionType::Peptide, 3, &flags);
+    let pyranose = factor(TorsionType::Sixring, 10, &flags);
+    let torsions = space.torsions(57);
+    let mut expected = peptide.torsions(5);
+    expected.extend(pyranose.torsions(7));
+    for (a, b) in torsions.iter().zip(expected.iter()) {
+        assert_float_absolute_eq!(a, b, 1e-12);
+    }
+}
+
+#[test]
+fn test_factors_keep_the_prompted_flags() {
+    let args = ["puckers", "--product", "sixring:50", "--amplitude", "0.3"]
+        .map(String::from)
+        .to_vec();
+    let flags = Flags::return_cli_arguments(args);
+
+    // the amplitude is passed on to the sixring, as the radius of its sphere
+    let pyranose = factor(TorsionType::Sixring, 50, &flags);
+    let rho = pyranose.coordinate_names().iter().position(|c| c == "RHO");
+    for i in 0..pyranose.len() {
+        assert_float_absolute_eq!(pyranose.coordinates(i)[rho.unwrap()], 0.3, 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected = "`--labels` can not be prompted with --product")]
+fn test_product_rejects_labels() {
+    let args = ["puckers", "--product", "fivering:5,sixring:10", "--labels"]
+        .map(String::from)
+        .to_vec();
+    Flags::return_cli_arguments(args);
+}
@@ -0,0 +1,44 @@
+use puckers::fivering::pseudorotation::{Pseudorotation, Sector};
+
+use assert_float_eq::*;
+
+/// The five endocyclic torsion angles along the pseudorotation wheel
+fn torsions(phase: f64, amplitude: f64) -> [f64; 5] {
+    [0, 1, 2, 3, 4].map(|j| {
+        amplitude * (phase.to_radians() + (4. * std::f64::consts::PI * (j as f64 - 2.) / 5.)).cos()
+    })
+}
+
+#[test]
+fn test_phase_from_torsions() {
+    for phase in [10., 162., 200., 341.] {
+        let wheel = Pseudorotation::from_torsions(torsions(phase, 38.));
+        assert_float_absolute_eq!(wheel.phase, phase, 0.001);
+        assert_float_absolute_eq!(wheel.amplitude, 38., 0.001);
+    }
+}
+
+#[test]
+fn test_nucleic_acid_forms() {
+    // A-form (C3'-endo) and B-form (C2'-endo) sugars
+    let north = Pseudorotation::from_torsions(torsions(18., 38.));
+    assert_eq!(north.form().label(), "3E");
+    assert_eq!(north.form().description(), "C3'-endo");
+    assert_eq!(north.sector(), Sector::North);
+
+    let south = Pseudorotation::from_zx_zy(-36., 11.7);
+    assert_eq!(south.form().label(), "2E");
+    assert_eq!(south.sector(), Sector::South);
+
+    let twist = Pseudorotation::from_zx_zy(40., 0.);
+    assert_eq!(twist.form().description(), "C3'-endo-C2'-exo");
+    assert_eq!(Sector::from_phase(270.), Sector::West);
+    assert_eq!(Sector::from_phase(90.), Sector::East);
+
+    // the origin of the grid has no phase
+    let planar = Pseudorotation::from_zx_zy(0., 0.);
+    assert!(planar.is_planar());
+    assert_eq!(planar.label(), "planar");
+    assert_eq!(planar.sector_letter(), "-");
+    assert_eq!(twist.label(), "3T2");
+}
@@ -0,0 +1,115 @@
+use puckers::arguments::Flags;
+use puckers::ring::puckering::PuckeringModes;
+use puckers::ring::reconstruct::{
+    close_ring, closure_residual, reconstruct_ring, BondParameters, CLOSURE_TOLERANCE,
+};
+use puckers::ring::sampling::puckering_grid;
+use puckers::ring::{endocyclic_torsions, nring_stream, ring_stream};
+use puckers::sixring::degeneracy::Degeneracy;
+use puckers::sixring::geometry::{Coordinate, LinAlg};
+use puckers::torsion_typing::SamplingSpace;
+
+use assert_float_eq::*;
+
+fn bond_lengths(atoms: &[Coordinate]) -> Vec<f64> {
+    let n = atoms.len();
+    (0..n)
+        .map(|j| atoms[j].subtract_arr(&atoms[(j + 1) % n]).norm())
+        .collect()
+}
+
+#[test]
+fn test_grid_dimensionality() {
+    assert_eq!(puckering_grid(4, 11, 0.25).len(), 11);
+    assert_eq!(puckering_grid(5, 11, 0.45).len(), 121);
+
+    // the sixring grid follows the Deserno sphere
+    assert!((625..=640).contains(&puckering_grid(6, 630, 0.67).len()));
+
+    for modes in puckering_grid(8, 500, 1.0) {
+        assert_eq!(modes.amplitudes.len(), 3);
+        assert_eq!(modes.phases.len(), 2);
+        assert_float_absolute_eq!(modes.total_amplitude(), 1.0, 1e-9);
+    }
+
+    // a cyclooctane from the command line; five torsions and five puckering coordinates
+    let args = ["puckers", "--ring", "8", "500"].map(String::from).to_vec();
+    let octane = nring_stream(&Flags::return_cli_arguments(args));
+    assert_eq!(octane.torsion_names().len(), 5);
+    assert_eq!(
+        octane.coordinate_names(),
+        vec!["Q2", "PHI2", "Q3", "PHI3", "Q4"]
+    );
+    assert_eq!(octane.len(), puckering_grid(8, 500, 1.0).len());
+}
+
+#[test]
+fn test_reconstruct_chair() {
+    // a perfect chair only has a q_3 amplitude
+    let chair = PuckeringModes::from_cartesian(6, &[0., 0., 0.63]);
+    let atoms = reconstruct_ring(&chair.local_elevation(), &BondParameters::default());
+
+    for length in bond_lengths(&atoms) {
+        assert_float_absolute_eq!(length, 1.54, 0.01);
+    }
+
+    // torsion angles alternate in sign and are equal in size
+    let nu = endocyclic_torsions(&atoms);
+    for j in 0..6 {
+        assert_float_absolute_eq!(nu[j], -nu[(j + 1) % 6], 0.5);
+        assert!(nu[j].abs() > 50. && nu[j].abs() < 65.);
+    }
+}
+
+#[test]
+fn test_reconstruction_keeps_the_puckering() {
+    // a twist-boat-like sixring and a twisted septanose
+    for (size, x) in [(6, vec![0.3, 0.4, -0.35]), (7, vec![0.2, -0.5, 0.45, 0.1])] {
+        let modes = PuckeringModes::from_cartesian(size, &x);
+        let atoms = reconstruct_ring(&modes.local_elevation(), &BondParameters::default());
+        let analysed = PuckeringModes::from_atoms(&atoms);
+
+        for (q, expected) in analysed.amplitudes.iter().zip(modes.amplitudes.iter()) {
+            assert_float_absolute_eq!(q, expected, 1e-9);
+        }
+        for (phi, expected) in analysed.phases.iter().zip(modes.phases.iter()) {
+            assert_float_absolute_eq!(phi, expected, 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_seven_membered_ring() {
+    let septanose = ring_stream(7, 200, 0.8, &BondParameters::default());
+
+    assert_eq!(septanose.dropped.total(), 0);
+    assert_eq!(septanose.torsions.len(), septanose.modes.len());
+    for i in 0..septanose.len() {
+        assert_eq!(septanose.torsions(i).len(), 4);
+        assert!(septanose.torsions(i).iter().all(|nu| nu.is_finite()));
+    }
+}
+
+#[test]
+fn test_rings_that_do_not_close_are_dropped() {
+    let bonds = BondParameters::default();
+
+    // a fourring puckered beyond its bond length can not be closed
+    let folded = PuckeringModes::from_cartesian(4, &[1.6]);
+    let atoms = reconstruct_ring(&folded.local_elevation(), &bonds.for_pucker(&folded));
+    assert!(closure_residual(&atoms, &bonds) > CLOSURE_TOLERANCE);
+    assert_eq!(
+        close_ring(&folded.local_elevation(), &bonds.for_pucker(&folded)),
+        Err(Degeneracy::Closure)
+    );
+
+    // a septanose at a far too large amplitude loses (some of) its points, the rest closes
+    let grid = puckering_grid(7, 200, 1.5).len();
+    let septanose = ring_stream(7, 200, 1.5, &bonds);
+    assert!(septanose.dropped.count(Degeneracy::Closure) > 0);
+    assert_eq!(septanose.len() + septanose.dropped.total(), grid);
+    for mode in &septanose.modes {
+        let atoms = reconstruct_ring(&mode.local_elevation(), &bonds);
+        assert!(closure_residual(&atoms, &bonds) <= CLOSURE_TOLERANCE);
+    }
+}
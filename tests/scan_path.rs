@@ -0,0 +1,68 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::fivering::fivering_path;
+use puckers::fivering::pseudorotation::Pseudorotation;
+use puckers::sixring::sphere_regions::angular_distance;
+use puckers::sixring::{sixring_conformation, sixring_stream};
+use puckers::torsion_typing::{SamplingSpace, TorsionType};
+
+#[test]
+fn test_sixring_scan_along_the_arc() {
+    let mut flags = Flags::new();
+    flags.torsion_type = Some(TorsionType::Sixring);
+    flags.num = 7;
+    // 4C1 to 1,4B
+    flags.path = Some([[0., 0.], [90., 240.]]);
+
+    let stream = sixring_stream(&flags);
+    assert_eq!(stream.len(), 7);
+
+    let points: Vec<[f64; 2]> = (0..stream.len())
+        .map(|i| {
+            let c = stream.coordinates(i);
+            [c[1], c[2]]
+        })
+        .collect();
+    // evenly spaced, 15 degrees apart, ending on the boat
+    for pair in points.windows(2) {
+        let d = angular_distance(pair[0][0], pair[0][1], pair[1][0], pair[1][1]);
+        assert_float_absolute_eq!(d.to_degrees(), 15., 1e-9);
+    }
+    assert_float_absolute_eq!(points[6][0].to_degrees(), 90., 1e-9);
+    assert_float_absolute_eq!(points[6][1].to_degrees(), 240., 1e-9);
+
+    let torsions = stream.torsions(3);
+    let expected = sixring_conformation(0.67, points[3][0], points[3][1])
+        .unwrap()
+        .alphas;
+    for (a, b) in torsions.iter().zip(expected.iter()) {
+        assert_float_absolute_eq!(a, b, 1e-12);
+    }
+}
+
+#[test]
+fn test_fivering_line_and_phase_paths() {
+    let mut flags = Flags::new();
+    flags.num = 5;
+
+    let line = fivering_path(&flags, [[-40., 0.], [40., 20.]]);
+    assert_eq!(line.len(), 5);
+    assert_eq!(line.coordinates(2), vec![0., 10.]);
+
+    // from P = 350 to P = 30 the short way, through P = 0, at an amplitude of 38
+    flags.phase = true;
+    let wheel = fivering_path(&flags, [[350., 38.], [30., 38.]]);
+    let phases: Vec<f64> = (0..wheel.len())
+        .map(|i| {
+            let c = wheel.coordinates(i);
+            let p = Pseudorotation::from_zx_zy(c[0], c[1]);
+            assert_float_absolute_eq!(p.amplitude, 38., 1e-9);
+            p.phase
+        })
+        .collect();
+    assert_float_absolute_eq!(wheel.coordinates(1)[0], 38., 1e-9);
+    assert_float_absolute_eq!(wheel.coordinates(1)[1], 0., 1e-9);
+    assert_float_absolute_eq!(phases[2], 10., 1e-9);
+    assert_float_absolute_eq!(phases[4], 30., 1e-9);
+}
@@ -0,0 +1,51 @@
+use puckers::arguments::Flags;
+use puckers::ring::endocyclic_torsions;
+use puckers::ring::puckering::PuckeringModes;
+use puckers::ring::reconstruct::reconstruct_ring;
+use puckers::sevenring::{sevenring, sevenring_bonds};
+use puckers::sixring::geometry::LinAlg;
+use puckers::torsion_typing::TorsionType;
+
+use assert_float_eq::*;
+
+#[test]
+fn test_sevenring_axes_generation() {
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Sevenring),
+        num: 300,
+        amplitude: Some(0.7),
+        bond_lengths: Some(vec![1.50]),
+        ..Flags::new()
+    };
+
+    let septanoses = sevenring(&flag);
+    let amount = septanoses.nu0.len();
+
+    // approximately NUM points on the hypersphere, all at the prompted total amplitude
+    assert!((270..=330).contains(&amount), "{}", amount);
+    for i in 0..amount {
+        let q = (septanoses.q2[i].powi(2) + septanoses.q3[i].powi(2)).sqrt();
+        assert_float_absolute_eq!(q, 0.7, 1e-9);
+    }
+
+    // the rebuilt septanoses have the prompted bond length, the one that closes the ring included,
+    // and their first four endocyclic torsions are the ones written out
+    for i in [0, amount / 3, amount / 2, amount - 1] {
+        let x = [
+            septanoses.q2[i] * septanoses.phi2[i].cos(),
+            septanoses.q2[i] * septanoses.phi2[i].sin(),
+            septanoses.q3[i] * septanoses.phi3[i].cos(),
+            septanoses.q3[i] * septanoses.phi3[i].sin(),
+        ];
+        let modes = PuckeringModes::from_cartesian(7, &x);
+        let atoms = reconstruct_ring(&modes.local_elevation(), &sevenring_bonds(&flag));
+
+        for j in 0..7 {
+            let bond = atoms[j].subtract_arr(&atoms[(j + 1) % 7]).norm();
+            assert_float_absolute_eq!(bond, 1.50, 0.02);
+        }
+        let nu = endocyclic_torsions(&atoms);
+        assert_float_absolute_eq!(septanoses.nu0[i], nu[0], 1e-6);
+        assert_float_absolute_eq!(septanoses.nu3[i], nu[3], 1e-6);
+    }
+}
@@ -1,5 +1,6 @@
 use puckers::arguments::Flags;
-use puckers::sixring::sixring;
+use puckers::sixring::geometry::dihedral;
+use puckers::sixring::{generate_sixring, sixring};
 use puckers::torsion_typing::TorsionType;
 
 use assert_float_eq::*;
@@ -9,6 +10,7 @@ fn test_sixring_axes_generation() {
     let flag = Flags {
         torsion_type: Some(TorsionType::Sixring),
         num: 630,
+        ..Flags::new()
     };
 
     let sixring_axes = sixring(&flag);
@@ -21,3 +23,35 @@ fn test_sixring_axes_generation() {
     assert_float_absolute_eq!(sixring_axes.alpha2[369], -149.389, 0.001);
     assert_float_absolute_eq!(sixring_axes.alpha3[369], -140.362, 0.001);
 }
+
+#[test]
+fn test_sixring_generation_carries_every_step() {
+    let flag = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 630,
+        ..Flags::new()
+    };
+
+    let generation = generate_sixring(&flag);
+    let amount = generation.sphere.amount;
+
+    assert_eq!(generation.elevations.nrows(), amount);
+    assert_eq!(generation.atoms.len(), amount);
+    assert_eq!(generation.pyranose.alpha1.len(), amount);
+
+    // the reconstructed atoms carry the local elevation as their z-coordinate
+    for i in [0, amount / 2, amount - 1] {
+        let ring = &generation.atoms[i];
+        let z = [
+            ring.p1[2], ring.p2[2], ring.p3[2], ring.p4[2], ring.p5[2], ring.p6[2],
+        ];
+        for (j, z_j) in z.iter().enumerate() {
+            assert_float_absolute_eq!(*z_j, generation.elevations[[i, j]], 1e-12);
+        }
+        assert_float_absolute_eq!(
+            generation.pyranose.alpha2[i],
+            dihedral(ring.p1, ring.p3, ring.p5, ring.p4),
+            1e-12
+        );
+    }
+}
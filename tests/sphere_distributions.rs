@@ -0,0 +1,71 @@
+use puckers::sixring::equidistance_sphere::equidistance_sphere;
+use puckers::sixring::sphere_distributions::{
+    fibonacci_sphere, healpix_nside, healpix_sphere, icosahedral_frequency, icosahedral_sphere,
+    SphereDistribution,
+};
+
+use assert_float_eq::*;
+
+#[test]
+fn test_fibonacci_exact_amount() {
+    let sphere = fibonacci_sphere(630);
+
+    assert_eq!(sphere.amount, 630);
+    assert_eq!(sphere.theta.len(), 630);
+    // the golden spiral runs from the north pole to the south pole
+    assert!(sphere.points[0].theta < sphere.points[629].theta);
+}
+
+#[test]
+fn test_healpix_nested() {
+    assert_eq!(healpix_nside(630), 8);
+
+    let coarse = healpix_sphere(2);
+    let fine = healpix_sphere(4);
+    assert_eq!(coarse.amount, 48);
+    assert_eq!(fine.amount, 192);
+
+    // the children of a pixel surround the center of their parent pixel
+    for p in 0..coarse.amount {
+        let theta = (0..4).map(|c| fine.points[4 * p + c].theta).sum::<f64>() / 4.;
+        assert_float_absolute_eq!(theta, coarse.points[p].theta, 0.1);
+    }
+}
+
+#[test]
+fn test_icosahedron_poles() {
+    assert_eq!(icosahedral_frequency(630), 8);
+
+    let sphere = icosahedral_sphere(4);
+    assert_eq!(sphere.amount, 162);
+
+    // both chairs are part of the grid
+    assert_float_absolute_eq!(sphere.points[0].theta, 0., 0.0001);
+    assert_float_absolute_eq!(sphere.points[161].theta, std::f64::consts::PI, 0.0001);
+}
+
+#[test]
+fn test_points_carry_their_layer() {
+    let sphere = equidistance_sphere(630);
+
+    assert_eq!(sphere.points.len(), sphere.amount);
+    for point in sphere.iter() {
+        assert_eq!(point.theta, sphere.theta[point.layer]);
+    }
+    // the layers are walked through from the north to the south pole
+    assert!(sphere.points.windows(2).all(|w| w[0].layer <= w[1].layer));
+}
+
+#[test]
+fn test_no_points_asked_for() {
+    for distribution in [
+        SphereDistribution::Deserno,
+        SphereDistribution::Fibonacci,
+        SphereDistribution::Healpix,
+        SphereDistribution::Icosahedron,
+    ] {
+        let sphere = distribution.generate(0);
+        assert_eq!(sphere.amount, 0);
+        assert!(sphere.points.is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use puckers::sixring::conformers::{find_conformer, CANONICAL_CONFORMERS};
+use puckers::sixring::sphere_distributions::SphereDistribution;
+use puckers::sixring::sphere_regions::{angular_distance, sphere_in_regions, SphereRegion};
+
+use assert_float_eq::*;
+
+#[test]
+fn test_canonical_labels() {
+    let labels: Vec<String> = CANONICAL_CONFORMERS
+        .iter()
+        .map(|c| c.default_label())
+        .collect();
+
+    for expected in [
+        "4C1", "1C4", "3,OB", "B1,4", "OS2", "OH5", "4H3", "E2", "EO",
+    ] {
+        assert!(labels.contains(&expected.to_string()), "{}", expected);
+    }
+
+    let boat = find_conformer("b2,5").unwrap();
+    assert_float_absolute_eq!(boat.theta, 90., 0.001);
+    assert_float_absolute_eq!(boat.phi, 300., 0.001);
+}
+
+#[test]
+fn test_cap_around_conformer() {
+    let region = SphereRegion::from_query("conformer:1C4,30").unwrap();
+    let sphere = sphere_in_regions(SphereDistribution::Fibonacci, 100, &[region]);
+
+    // the density is refined so that the amount of points is close to the prompted amount
+    assert!((90..=110).contains(&sphere.amount));
+    for p in sphere.iter() {
+        let d = angular_distance(p.theta, p.phi, std::f64::consts::PI, 0.);
+        assert!(d <= 30_f64.to_radians());
+    }
+
+    // the comma of a boat label is not taken for the radius
+    let boat = SphereRegion::from_query("conformer:B1,4,30").unwrap();
+    assert_eq!(
+        boat,
+        SphereRegion::Cap {
+            theta: 90_f64.to_radians(),
+            phi: 60_f64.to_radians(),
+            radius: 30_f64.to_radians(),
+        }
+    );
+    assert!(SphereRegion::from_query("conformer:B1,4").is_err());
+}
+
+#[test]
+fn test_wrapping_phi_band() {
+    let region = SphereRegion::from_query("phi:330,30").unwrap();
+
+    assert!(region.contains(1.0, 0.1));
+    assert!(region.contains(1.0, 6.2));
+    assert!(!region.contains(1.0, 3.1));
+    assert!(SphereRegion::from_query("square:1,2").is_err());
+}
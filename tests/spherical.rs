@@ -0,0 +1,91 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::landscape::spherical::{real_harmonics, sixring_surface, SphereSurface};
+use puckers::landscape::table::parse_table;
+use puckers::landscape::SurfaceFit;
+use puckers::sixring::sphere_distributions::SphereDistribution;
+
+/// A smooth surface on the sphere, made of harmonics up to degree two
+fn surface(theta: f64, phi: f64) -> f64 {
+    1. + theta.cos() + theta.sin().powi(2) * (2. * phi).cos()
+}
+
+fn sphere(num: u64) -> Vec<[f64; 2]> {
+    SphereDistribution::Fibonacci
+        .generate(num)
+        .iter()
+        .map(|p| [p.theta, p.phi])
+        .collect()
+}
+
+#[test]
+fn test_real_harmonics_are_orthonormal() {
+    // integrate over a dense sphere : every point covers 4 PI / N
+    let points = sphere(20000);
+    let weight = 4. * std::f64::consts::PI / points.len() as f64;
+    let basis: Vec<Vec<f64>> = points
+        .iter()
+        .map(|p| real_harmonics(3, p[0], p[1]))
+        .collect();
+
+    assert_eq!(basis[0].len(), 16);
+    for a in [0, 2, 5, 9, 15] {
+        for b in [0, 2, 5, 9, 15] {
+            let product: f64 = basis.iter().map(|y| y[a] * y[b]).sum::<f64>() * weight;
+            let expected = if a == b { 1. } else { 0. };
+            assert_float_absolute_eq!(product, expected, 1e-3);
+        }
+    }
+}
+
+#[test]
+fn test_harmonics_and_rbf_fits() {
+    let points = sphere(200);
+    let values: Vec<f64> = points.iter().map(|p| surface(p[0], p[1])).collect();
+
+    let harmonics = SphereSurface::new(&points, &values, SurfaceFit::Harmonics(2)).unwrap();
+    assert!(harmonics.residual_summary().1 < 1e-9);
+    assert_float_absolute_eq!(harmonics.evaluate(1.0, 4.0), surface(1.0, 4.0), 1e-9);
+
+    // too few points for the degree
+    assert!(SphereSurface::new(&points[..8], &values[..8], SurfaceFit::Harmonics(2)).is_err());
+
+    // passes through every point, and predicts the left-out points reasonably
+    let rbf = SphereSurface::new(&points, &values, SurfaceFit::Rbf(1.0)).unwrap();
+    assert_float_absolute_eq!(rbf.evaluate(points[17][0], points[17][1]), values[17], 1e-8);
+    let (rms, _) = rbf.residual_summary();
+    assert!(rms > 0. && rms < 0.1);
+}
+
+#[test]
+fn test_sixring_surface_from_table() {
+    let mut text = String::from(
+        "#    INDEX     ALPHA1     ALPHA2     ALPHA3        RHO      THETA        PHI           ENERGY     STATUS\n",
+    );
+    for (i, p) in sphere(100).iter().enumerate() {
+        let status = if i == 3 { "unconv" } else { "ok" };
+        text.push_str(&format!(
+            "{:>10} {:10.3} {:10.3} {:10.3} {:10.3} {:10.3} {:10.3} {:16.8} {:>10}\n",
+            i,
+            0.,
+            0.,
+            0.,
+            0.67,
+            p[0],
+            p[1],
+            surface(p[0], p[1]) / 627.509474,
+            status
+        ));
+    }
+
+    let flags = Flags {
+        fit: Some(SurfaceFit::Harmonics(2)),
+        ..Flags::new()
+    };
+    let (fitted, rows) = sixring_surface(&parse_table(&text).unwrap(), &flags).unwrap();
+    assert_eq!(rows.len(), 99);
+    assert!(!rows.contains(&3));
+    // the three decimals of the table limit the fit
+    assert!(fitted.residual_summary().1 < 0.01);
+}
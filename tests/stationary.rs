@@ -0,0 +1,92 @@
+use assert_float_eq::*;
+
+use puckers::arguments::Flags;
+use puckers::landscape::graph::NeighbourGraph;
+use puckers::landscape::stationary::{classify_point, find_stationary, newton, Stationary};
+use puckers::landscape::table::parse_table;
+use puckers::sixring::sphere_distributions::SphereDistribution;
+
+#[test]
+fn test_minimum_and_saddles_on_a_periodic_grid() {
+    // cos(phi) + cos(psi) on a 12 x 12 grid : one minimum, two saddles and one maximum
+    let n = 12;
+    let graph = NeighbourGraph::grid(n, n, true);
+    let energies: Vec<f64> = (0..n * n)
+        .map(|v| {
+            let (phi, psi) = ((v / n) as f64 * 30., (v % n) as f64 * 30.);
+            phi.to_radians().cos() + psi.to_radians().cos()
+        })
+        .collect();
+
+    let kinds: Vec<(usize, Stationary)> = (0..n * n)
+        .filter_map(|v| classify_point(&graph, &energies, v).map(|k| (v, k)))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (6, Stationary::Saddle),
+            (6 * n, Stationary::Saddle),
+            (6 * n + 6, Stationary::Minimum)
+        ]
+    );
+
+    // the open border of a grid that does not wrap around is left out
+    let open = NeighbourGraph::grid(n, n, false);
+    assert_eq!(classify_point(&open, &energies, 6), None);
+}
+
+#[test]
+fn test_sphere_graph_and_stationary_points() {
+    let points: Vec<[f64; 2]> = SphereDistribution::Fibonacci
+        .generate(400)
+        .iter()
+        .map(|p| [p.theta, p.phi])
+        .collect();
+    let graph = NeighbourGraph::sphere(&points);
+    for (i, around) in graph.neighbours.iter().enumerate() {
+        assert!((3..=8).contains(&around.len()));
+        assert!(around.iter().all(|&j| graph.neighbours[j].contains(&i)));
+    }
+
+    // two chairs at the poles, and three boats in between
+    let mut text =
+        String::from("#    INDEX        RHO      THETA        PHI           ENERGY     STATUS\n");
+    for (i, [theta, phi]) in points.iter().enumerate() {
+        let e = -theta.cos().powi(2) + 0.3 * theta.sin().powi(2) * (3. * phi).cos();
+        text.push_str(&format!(
+            "{:10} {:10.3} {:10.6} {:10.6} {:16.8} {:>10}\n",
+            i,
+            0.67,
+            theta,
+            phi,
+            -200. + 0.001 * e,
+            "ok"
+        ));
+    }
+    let table = parse_table(&text).unwrap();
+
+    let mut flags = Flags::new();
+    flags.refine = true;
+    let stationary = find_stationary(&table, &flags).unwrap();
+    let count = |kind| stationary.iter().filter(|p| p.kind == kind).count();
+    assert_eq!(count(Stationary::Minimum), 2);
+    assert_eq!(count(Stationary::Saddle), 3);
+
+    // the boats lie on the equator
+    for point in stationary.iter().filter(|p| p.kind == Stationary::Saddle) {
+        let ([theta, _], _) = point.refined.unwrap();
+        assert_float_absolute_eq!(theta, std::f64::consts::FRAC_PI_2, 1e-3);
+    }
+}
+
+#[test]
+fn test_newton_refinement() {
+    let saddle = |u: f64, v: f64| (u - 0.3).powi(2) - 2. * (v + 0.2).powi(2);
+    let [u, v] = newton(saddle, 1., Stationary::Saddle).unwrap();
+    assert_float_absolute_eq!(u, 0.3, 1e-6);
+    assert_float_absolute_eq!(v, -0.2, 1e-6);
+
+    // a saddle is not refined into a minimum, nor a point far away
+    assert!(newton(saddle, 1., Stationary::Minimum).is_none());
+    assert!(newton(|u, v| (u - 5.).powi(2) + v * v, 1., Stationary::Minimum).is_none());
+}
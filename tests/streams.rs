@@ -0,0 +1,41 @@
+use puckers::arguments::Flags;
+use puckers::sevenring::{sevenring, sevenring_stream};
+use puckers::sixring::{sixring, sixring_stream};
+use puckers::torsion_typing::{SamplingSpace, TorsionType};
+
+#[test]
+fn test_sixring_stream_matches_sixring() {
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Sixring),
+        num: 300,
+        ..Flags::new()
+    };
+
+    let pyranose = sixring(&flags);
+    let stream = sixring_stream(&flags);
+
+    assert_eq!(stream.len(), pyranose.len());
+    for (sample, i) in stream.samples(false).zip(0..) {
+        assert_eq!(sample.torsions, pyranose.torsions(i));
+        assert_eq!(sample.coordinates, pyranose.coordinates(i));
+    }
+}
+
+#[test]
+fn test_ring_stream_columns() {
+    let flags = Flags {
+        torsion_type: Some(TorsionType::Sevenring),
+        num: 50,
+        ..Flags::new()
+    };
+
+    let septanose = sevenring(&flags);
+    let stream = sevenring_stream(&flags);
+
+    assert_eq!(stream.torsion_names(), septanose.torsion_names());
+    assert_eq!(stream.coordinate_names(), septanose.coordinate_names());
+    for i in [0, stream.len() - 1] {
+        assert_eq!(stream.torsions(i), septanose.torsions(i));
+        assert_eq!(stream.coordinates(i), septanose.coordinates(i));
+    }
+}